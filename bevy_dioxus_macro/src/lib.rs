@@ -0,0 +1,314 @@
+use convert_case::{Case, Casing};
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    braced, parenthesized, parse_macro_input, DeriveInput, Ident, LitStr, Path, Token, Type,
+    Visibility,
+};
+
+/// `[vis] element => TraitName { attr, ... }`
+struct AttrsExtension {
+    vis: Visibility,
+    element: Path,
+    trait_name: Ident,
+    attrs: Punctuated<Path, Token![,]>,
+}
+
+impl Parse for AttrsExtension {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let element = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let trait_name = input.parse()?;
+        let content;
+        braced!(content in input);
+        let attrs = content.parse_terminated(Path::parse, Token![,])?;
+        Ok(Self {
+            vis,
+            element,
+            trait_name,
+            attrs,
+        })
+    }
+}
+
+/// Generates the `rsx!` extension trait of attrs registered at runtime with
+/// `app.register_dioxus_attr::<element>(attr)`, and their `HasIndex`.
+///
+/// ```ignore
+/// attrs_extension!(pub view => GameViewAttrs { health, team });
+/// ```
+///
+/// Each attr should only be listed once, as its `HasIndex` impl is generated here.
+#[proc_macro]
+pub fn attrs_extension(input: TokenStream) -> TokenStream {
+    let AttrsExtension {
+        vis,
+        element,
+        trait_name,
+        attrs,
+    } = parse_macro_input!(input as AttrsExtension);
+
+    let element_name = element.segments.last().unwrap().ident.to_string();
+    let element_extension = format_ident!("{}AttrsExtension", element_name.to_case(Case::Pascal));
+    let fns = attrs.iter().map(|attr| {
+        let fn_name = &attr.segments.last().unwrap().ident;
+        quote! {
+            fn #fn_name(self, value: impl ::bevy_dioxus::prelude::IntoAttributeValue<'a>) -> Self {
+                let (name, namespace, volatile) =
+                    <#attr as ::bevy_dioxus::ElementAttr>::ATTRIBUTE_DESCRIPTION;
+                self.push_attribute(name, namespace, value, volatile)
+            }
+        }
+    });
+    let attrs = attrs.iter();
+
+    quote! {
+        #vis trait #trait_name<'a>: ::bevy_dioxus::prelude::HasAttributes<'a> + Sized {
+            #(#fns)*
+        }
+
+        impl<'a, T: dioxus_elements::extensions::#element_extension<'a>> #trait_name<'a> for T {}
+
+        #(
+            impl ::bevy_dioxus::HasIndex for #attrs {
+                const INDEX: ::bevy_dioxus::AttrIndex = ::bevy_dioxus::CUSTOM_ATTR_INDEX;
+            }
+        )*
+    }
+    .into()
+}
+
+enum AttrTarget {
+    /// `style.a.b = Type`
+    Style(Vec<Ident>, Type),
+    /// `component = Type`
+    Component(Type),
+}
+
+struct ElementAttrArgs {
+    target: AttrTarget,
+    name: Option<LitStr>,
+}
+
+impl Parse for ElementAttrArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut target = None;
+        let mut name = None;
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            match key.to_string().as_str() {
+                "style" => {
+                    let mut fields = vec![];
+                    while input.peek(Token![.]) {
+                        input.parse::<Token![.]>()?;
+                        fields.push(input.parse()?);
+                    }
+                    if fields.is_empty() {
+                        return Err(syn::Error::new(key.span(), "expected `style.field = Type`"));
+                    }
+                    input.parse::<Token![=]>()?;
+                    target = Some(AttrTarget::Style(fields, input.parse()?));
+                }
+                "component" => {
+                    input.parse::<Token![=]>()?;
+                    target = Some(AttrTarget::Component(input.parse()?));
+                }
+                "name" => {
+                    input.parse::<Token![=]>()?;
+                    name = Some(input.parse()?);
+                }
+                _ => return Err(syn::Error::new(key.span(), "unknown element_attr argument")),
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        let Some(target) = target else {
+            return Err(input.error("expected `style.field = Type` or `component = Type`"));
+        };
+        Ok(Self { target, name })
+    }
+}
+
+/// Implements `ElementAttr` for a unit struct that sets a `Style` field or inserts a component.
+///
+/// ```ignore
+/// #[derive(ElementAttr)]
+/// #[element_attr(style.border.left = Val)]
+/// pub struct border_left;
+///
+/// #[derive(ElementAttr)]
+/// #[element_attr(component = BorderColor)]
+/// pub struct border_color;
+/// ```
+///
+/// `HasIndex` comes from the element defining the attr.
+#[proc_macro_derive(ElementAttr, attributes(element_attr))]
+pub fn derive_element_attr(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let ident = &input.ident;
+    let Some(attr) = input
+        .attrs
+        .iter()
+        .find(|n| n.path().is_ident("element_attr"))
+    else {
+        return syn::Error::new(ident.span(), "missing #[element_attr(...)]")
+            .to_compile_error()
+            .into();
+    };
+    let args = match attr.parse_args::<ElementAttrArgs>() {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let name = args
+        .name
+        .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+    let (value_type, set_value) = match args.target {
+        AttrTarget::Style(fields, ty) => (
+            ty,
+            quote! {
+                ::bevy_dioxus::StyleEntityExt::try_set_style(&mut *context.entity_ref, |style| {
+                    style.#(#fields).* = value.into();
+                });
+            },
+        ),
+        AttrTarget::Component(ty) => (
+            ty,
+            quote! {
+                context.entity_ref.insert(value.into());
+            },
+        ),
+    };
+
+    quote! {
+        impl ::bevy_dioxus::ElementAttr for #ident {
+            type Value = #value_type;
+
+            const TAG_NAME: &'static str = #name;
+
+            fn set_value(
+                &self,
+                context: &mut ::bevy_dioxus::SetAttrValueContext,
+                value: impl Into<Self::Value>,
+            ) {
+                #set_value
+            }
+        }
+    }
+    .into()
+}
+
+#[derive(Default)]
+struct DioxusElementArgs {
+    attrs: Vec<Path>,
+    composite_attrs: Vec<Path>,
+}
+
+impl Parse for DioxusElementArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = DioxusElementArgs::default();
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let paths = content.parse_terminated(Path::parse, Token![,])?;
+            match key.to_string().as_str() {
+                "attrs" => args.attrs.extend(paths),
+                "composite_attrs" => args.composite_attrs.extend(paths),
+                _ => return Err(syn::Error::new(key.span(), "unknown dioxus_element argument")),
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// Implements `ElementTypeBase` for an element defined outside of this crate, the `HasIndex` of
+/// its attrs, and its `rsx!` extension trait `{Name}AttrsExtension`.
+/// `ElementType` (how the element is spawned) is still implemented by hand.
+///
+/// ```ignore
+/// #[derive(DioxusElement, Reflect, Default, Clone, Copy)]
+/// #[dioxus_element(attrs(value))]
+/// pub struct health_bar;
+/// ```
+#[proc_macro_derive(DioxusElement, attributes(dioxus_element))]
+pub fn derive_dioxus_element(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let args = match input
+        .attrs
+        .iter()
+        .find(|n| n.path().is_ident("dioxus_element"))
+        .map(|n| n.parse_args::<DioxusElementArgs>())
+        .transpose()
+    {
+        Ok(args) => args.unwrap_or_default(),
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let tag_name = LitStr::new(&ident.to_string(), ident.span());
+    let extension = format_ident!("{}AttrsExtension", ident.to_string().to_case(Case::Pascal));
+    let attrs = &args.attrs;
+    let composite_attrs = &args.composite_attrs;
+    let indices = 0..attrs.len() as u16;
+    let attr_fns = attrs.iter().map(|attr| {
+        let fn_name = &attr.segments.last().unwrap().ident;
+        quote! {
+            fn #fn_name(self, value: impl ::bevy_dioxus::prelude::IntoAttributeValue<'a>) -> Self {
+                let (name, namespace, volatile) =
+                    <#attr as ::bevy_dioxus::ElementAttr>::ATTRIBUTE_DESCRIPTION;
+                self.push_attribute(name, namespace, value, volatile)
+            }
+        }
+    });
+    let composite_attr_fns = composite_attrs.iter().map(|attr| {
+        let fn_name = &attr.segments.last().unwrap().ident;
+        quote! {
+            fn #fn_name(self, value: impl ::bevy_dioxus::prelude::IntoAttributeValue<'a>) -> Self {
+                let (name, namespace, volatile) =
+                    <#attr as ::bevy_dioxus::ElementCompositeAttr>::ATTRIBUTE_DESCRIPTION;
+                self.push_attribute(name, namespace, value, volatile)
+            }
+        }
+    });
+
+    quote! {
+        impl ::bevy_dioxus::CommonAttrs for #ident {}
+        impl ::bevy_dioxus::CommonCompositeAttrs for #ident {}
+
+        #(
+            impl ::bevy_dioxus::HasIndex for #attrs {
+                const INDEX: ::bevy_dioxus::AttrIndex =
+                    <#ident as ::bevy_dioxus::CommonAttrs>::ATTRS.len() as ::bevy_dioxus::AttrIndex
+                        + #indices;
+            }
+        )*
+
+        impl ::bevy_dioxus::ElementTypeBase for #ident {
+            const TAG_NAME: &'static str = #tag_name;
+            const ATTRS: &'static [&'static [&'static dyn ::bevy_dioxus::ElementAttrUntyped]] = &[
+                <Self as ::bevy_dioxus::CommonAttrs>::ATTRS,
+                &[#(&#attrs),*],
+            ];
+            const COMPOSITE_ATTRS: &'static [&'static [&'static dyn ::bevy_dioxus::ElementCompositeAttrUntyped]] = &[
+                <Self as ::bevy_dioxus::CommonCompositeAttrs>::ATTRS,
+                &[#(&#composite_attrs),*],
+            ];
+        }
+
+        #vis trait #extension<'a>: ::bevy_dioxus::prelude::HasAttributes<'a> + Sized {
+            #(#attr_fns)*
+            #(#composite_attr_fns)*
+        }
+
+        impl<'a, T: #extension<'a>> ::bevy_dioxus::_CommonAttrsExtension<'a, #ident> for T {}
+        impl<'a, T: #extension<'a>> ::bevy_dioxus::_CommonCompositeAttrsExtension<'a, #ident> for T {}
+    }
+    .into()
+}
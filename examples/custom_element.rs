@@ -0,0 +1,169 @@
+#![allow(non_snake_case)]
+#![allow(non_camel_case_types)]
+
+use bevy::ecs::world::EntityMut;
+use bevy_dioxus::prelude::*;
+use bevy_dioxus::SetAttrValueContext;
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, DioxusPlugin::new(Root)))
+        .insert_resource(
+            TailwindTheme::default().with_custom_color("brand", Color::rgb_u8(255, 136, 0)),
+        )
+        .register_dioxus_element::<health_bar>()
+        .register_dioxus_attr::<view>(health)
+        .register_type::<HealthBar>()
+        .register_type::<HitButton>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_health_bars);
+
+    app.run();
+}
+
+fn setup(mut commands: Commands, mut keyframes: ResMut<UiKeyframes>) {
+    commands.spawn(Camera2dBundle::default());
+    keyframes
+        .add(
+            "pulse",
+            Keyframes::new()
+                .from(KeyframeValues::default().scale(Vec3::ONE))
+                .to(KeyframeValues::default().scale(Vec3::splat(1.2))),
+        )
+        .add(
+            "flash",
+            Keyframes::new().at(0.5, KeyframeValues::default().background(Color::RED)),
+        );
+}
+
+#[derive(Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component, Default)]
+pub struct HealthBar {
+    pub value: f32,
+}
+
+/// Attached to the button through the `components` attr
+#[derive(Component, Reflect, Default, Clone, Copy, PartialEq)]
+#[reflect(Component, Default, PartialEq)]
+pub struct HitButton;
+
+fn update_health_bars(mut bars: Query<(&HealthBar, &mut BackgroundColor), Changed<HealthBar>>) {
+    for (bar, mut background) in bars.iter_mut() {
+        let value = bar.value.clamp(0., 1.);
+        background.0 = Color::rgb(1. - value, value, 0.);
+    }
+}
+
+#[derive(DioxusElement, Reflect, Default, Debug, Clone, Copy)]
+#[dioxus_element(attrs(value))]
+pub struct health_bar;
+
+pub struct value;
+
+impl ElementAttr for value {
+    type Value = f32;
+
+    const TAG_NAME: &'static str = stringify!(value);
+
+    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+        context.entity_ref.insert(HealthBar {
+            value: value.into(),
+        });
+    }
+}
+
+/// `health` attr added to the built-in `view`
+pub struct health;
+
+impl ElementAttr for health {
+    type Value = f32;
+
+    const TAG_NAME: &'static str = stringify!(health);
+
+    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+        context.entity_ref.insert(HealthBar {
+            value: value.into(),
+        });
+    }
+}
+
+attrs_extension!(pub view => GameViewAttrs { health });
+
+impl ElementType for health_bar {
+    fn spawn<'w>(&self, world: &'w mut World) -> EntityMut<'w> {
+        world.spawn((NodeBundle::default(), HealthBar::default()))
+    }
+}
+
+mod dioxus_elements {
+    pub use bevy_dioxus::prelude::dioxus_elements::*;
+
+    pub use super::health_bar;
+
+    pub mod extensions {
+        pub use bevy_dioxus::prelude::dioxus_elements::extensions::*;
+
+        pub use super::super::HealthBarAttrsExtension;
+    }
+}
+
+pub fn Root(cx: Scope) -> Element {
+    let health = use_state(cx, || 1.0f32);
+    let flash = use_animation(cx);
+    let pulse = use_animation(cx);
+    let paused = use_state(cx, || false);
+    let cmd_sender = use_cmd_sender(cx);
+    render! {
+        view {
+            class: "p-4 flex-col gap-2 lg:p-8 lg:gap-4",
+            health_bar {
+                class: "w-200 h-4 transition-colors duration-300",
+                value: **health,
+            }
+            view {
+                class: "w-100 h-4",
+                health: **health,
+            }
+            view {
+                class: "p-2 bg-gray-200 hover:bg-gray-100 active:bg-blue-200 transition duration-200",
+                components: ui_components![HitButton],
+                onclick: move |_| {
+                    health.set((**health - 0.1).max(0.));
+                    flash.restart();
+                },
+                "Hit"
+            }
+            view {
+                class: "w-[400px] h-4 bg-[#e5e7eb] border-2 border-gray-900/[0.35]",
+                animation: "flash 300ms ease-out",
+                animation_handle: flash.handle(),
+                onanimationend: move |e: Event<UiEvent<AnimationEndEvent>>| info!("{} ended", e.data.name),
+            }
+            view {
+                class: "p-2 bg-brand text-lg",
+                animation: "pulse 800ms ease-in-out infinite alternate",
+                animation_handle: pulse.handle(),
+                onclick: move |_| {
+                    if **paused {
+                        pulse.play();
+                    } else {
+                        pulse.pause();
+                    }
+                    paused.set(!**paused);
+                },
+                "Pulse"
+            }
+            view {
+                class: "p-2 bg-gray-200 dark:bg-gray-700 dark:text-white",
+                onclick: move |_| {
+                    cmd_sender.send_cmd(|world: &mut World| {
+                        let mut color_scheme = world.resource_mut::<UiColorScheme>();
+                        color_scheme.dark = !color_scheme.dark;
+                    });
+                },
+                "Toggle dark"
+            }
+        }
+    }
+}
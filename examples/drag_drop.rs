@@ -0,0 +1,60 @@
+#![allow(non_snake_case)]
+
+use bevy_dioxus::prelude::*;
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, DioxusPlugin::new(Root)))
+        .add_systems(Startup, setup);
+
+    app.run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
+
+pub fn Root(cx: Scope) -> Element {
+    let items = use_state(cx, || {
+        vec!["Sword", "Shield", "Potion", "Bow", "Arrows"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>()
+    });
+    let hovered = use_state(cx, || None::<usize>);
+    render! {
+        view {
+            class: "p-4 flex-col gap-2 w-200",
+            for (index, item) in items.iter().enumerate() {
+                view {
+                    key: "{item}",
+                    class: "p-2 bg-white border border-gray-300 {hovered_class(**hovered == Some(index))}",
+                    draggable: true,
+                    ondragstart: move |e: Event<UiPointerEvent<DragStart>>| e.data.set_drag_data(index),
+                    ondragenter: move |_: Event<UiPointerEvent<DragEnter>>| hovered.set(Some(index)),
+                    ondragleave: move |_: Event<UiPointerEvent<DragLeave>>| hovered.set(None),
+                    ondrop: move |e: Event<UiPointerEvent<Drop>>| {
+                        hovered.set(None);
+                        let Some(from) = e.data.drag_data::<usize>() else {
+                            return;
+                        };
+                        items.with_mut(|items| {
+                            let item = items.remove(from);
+                            items.insert(index, item);
+                        });
+                    },
+                    "{item}"
+                }
+            }
+        }
+    }
+}
+
+fn hovered_class(is_hovered: bool) -> &'static str {
+    if is_hovered {
+        "border-blue-500"
+    } else {
+        ""
+    }
+}
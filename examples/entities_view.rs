@@ -6,6 +6,14 @@ fn main() {
     let mut app = App::new();
 
     app.add_plugins((DefaultPlugins, DioxusPlugin::new(Root)))
+        .insert_resource(
+            TailwindTheme::default()
+                .with_class("btn", "p-2 bg-gray-200 hover:bg-gray-100 active:bg-blue-200"),
+        )
+        .add_ui_stylesheet(
+            UiStyleSheet::default()
+                .with_rule("#world-entities text", [("text_color", "#1f2937".into())]),
+        )
         .add_systems(Startup, setup);
 
     app.run();
@@ -36,11 +44,11 @@ pub fn Root(cx: Scope) -> Element {
 
 #[component]
 fn WorldView(cx: Scope) -> Element {
-    let handle_refresh_click = move |_: Event<Pointer<Click>>| {
+    let handle_refresh_click = move |_: Event<UiPointerEvent<Click>>| {
         cx.needs_update();
     };
     let cmd_sender = use_cmd_sender(cx);
-    let handle_spawn_click = move |_: Event<Pointer<Click>>| {
+    let handle_spawn_click = move |_: Event<UiPointerEvent<Click>>| {
         cmd_sender.send_cmd(|world: &mut World| {
             world.resource_scope(|world, mut meshes: Mut<Assets<Mesh>>| {
                 world.resource_scope(|world, mut materials: Mut<Assets<StandardMaterial>>| {
@@ -66,26 +74,26 @@ fn WorldView(cx: Scope) -> Element {
     render! {
         view{
             name: "world-view",
-            class: "min-w-60 bg-gray-300 p-4 flex-col flex-grow-1 items-stretch gap-2",
+            class: "min-w-60 bg-gray-300 p-4 flex-col grow items-stretch gap-2",
             view {
                 name: "world-view-toolbar",
                 class: "flex-row gap-2",
                 view {
                     name: "refresh-btn",
-                    class: "p-2 bg-gray-200 hover:bg-gray-100 active:bg-blue-200",
+                    class: "btn",
                     onclick: handle_refresh_click,
                     "refresh"
                 }
                 view {
                     name: "spawn-btn",
-                    class: "p-2 bg-gray-200 hover:bg-gray-100 active:bg-blue-200",
+                    class: "btn",
                     onclick: handle_spawn_click,
                     "spawn"
                 }
             }
-            view {
+            scroll_view {
                 name: "world-entities",
-                class: "flex-col flex-grow-1 items-stretch",
+                class: "flex-col grow items-stretch",
                 for entity in entity_infos {
                     EntityItem {
                         data: entity,
@@ -156,7 +164,7 @@ fn EntityItem(cx: Scope, data: EntityInfo, level: u8) -> Element {
     render! {
         view {
             name: "entity-item",
-            class: "p-1 bg-gray-200 hover:bg-gray-100 active:bg-blue-200 items-center",
+            class: "btn p-1 items-center",
             for _ in (0..*level) {
                 view {
                     name: "entity-item-indicator",
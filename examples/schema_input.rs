@@ -50,11 +50,26 @@ fn setup(mut commands: Commands) {
 }
 
 pub fn Root(cx: Scope) -> Element {
+    let submitted = use_state(cx, String::new);
     render! {
         view {
             class: "p-4 flex-col mt-2 gap-2 text-red",
             input{
-                class: "text-38 h-40"
+                class: "text-38 h-40",
+                placeholder: "Type and press Enter",
+                onsubmit: move |e: Event<UiEvent<TextSubmitEvent>>| submitted.set(e.data.value.clone()),
+            }
+            "Submitted: {submitted}"
+            input{
+                class: "w-200",
+                multiline: true,
+                rows: 6,
+                placeholder: "Notes",
+            }
+            input{
+                class: "h-40",
+                password: true,
+                max_length: 16,
             }
         }
     }
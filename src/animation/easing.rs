@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+
+use crate::MyFromStr;
+
+/// Timing function of transitions and animations, values follow tailwind
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Default, PartialEq)]
+pub enum Easing {
+    Linear,
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::EaseInOut
+    }
+}
+
+impl Easing {
+    pub fn control_points(self) -> Option<(f32, f32, f32, f32)> {
+        Some(match self {
+            Easing::Linear => return None,
+            Easing::Ease => (0.25, 0.1, 0.25, 1.0),
+            Easing::EaseIn => (0.4, 0.0, 1.0, 1.0),
+            Easing::EaseOut => (0.0, 0.0, 0.2, 1.0),
+            Easing::EaseInOut => (0.4, 0.0, 0.2, 1.0),
+            Easing::CubicBezier(x1, y1, x2, y2) => (x1, y1, x2, y2),
+        })
+    }
+
+    /// Maps the linear progress `t` in `0..=1` to the eased progress
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        let Some((x1, y1, x2, y2)) = self.control_points() else {
+            return t;
+        };
+        let bezier = |a: f32, b: f32, s: f32| {
+            let inv = 1.0 - s;
+            3.0 * inv * inv * s * a + 3.0 * inv * s * s * b + s * s * s
+        };
+        // solve x(s) = t with newton iterations, falling back to bisection
+        let mut s = t;
+        for _ in 0..8 {
+            let x = bezier(x1, x2, s) - t;
+            if x.abs() < 1e-5 {
+                return bezier(y1, y2, s);
+            }
+            let inv = 1.0 - s;
+            let dx = 3.0 * inv * inv * x1 + 6.0 * inv * s * (x2 - x1) + 3.0 * s * s * (1.0 - x2);
+            if dx.abs() < 1e-6 {
+                break;
+            }
+            s -= x / dx;
+        }
+        let (mut low, mut high) = (0.0, 1.0);
+        s = t;
+        for _ in 0..32 {
+            let x = bezier(x1, x2, s);
+            if (x - t).abs() < 1e-5 {
+                break;
+            }
+            if x < t {
+                low = s;
+            } else {
+                high = s;
+            }
+            s = (low + high) / 2.0;
+        }
+        bezier(y1, y2, s)
+    }
+}
+
+impl MyFromStr for Easing {
+    fn from_str(s: &str) -> Option<Self> {
+        Some(match s.trim() {
+            "linear" => Easing::Linear,
+            "ease" => Easing::Ease,
+            "ease-in" => Easing::EaseIn,
+            "ease-out" => Easing::EaseOut,
+            "ease-in-out" => Easing::EaseInOut,
+            s => {
+                let args = s.strip_prefix("cubic-bezier(")?.strip_suffix(')')?;
+                let args = args
+                    .split(',')
+                    .map(|n| n.trim().parse::<f32>().ok())
+                    .collect::<Option<Vec<_>>>()?;
+                let [x1, y1, x2, y2] = args[..] else {
+                    return None;
+                };
+                Easing::CubicBezier(x1, y1, x2, y2)
+            }
+        })
+    }
+}
+
+/// Interpolation between two attr values
+pub trait Lerp {
+    fn lerp(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for Vec3 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Vec3::lerp(*self, *to, t)
+    }
+}
+
+impl Lerp for Quat {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        self.slerp(*to, t)
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        let [r, g, b, a] = self.as_rgba_f32();
+        let [to_r, to_g, to_b, to_a] = to.as_rgba_f32();
+        Color::rgba(r.lerp(&to_r, t), g.lerp(&to_g, t), b.lerp(&to_b, t), a.lerp(&to_a, t))
+    }
+}
+
+/// Only values of the same unit are interpolated, others jump at the end
+impl Lerp for Val {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        match (*self, *to) {
+            (Val::Px(a), Val::Px(b)) => Val::Px(a.lerp(&b, t)),
+            (Val::Percent(a), Val::Percent(b)) => Val::Percent(a.lerp(&b, t)),
+            (Val::Vw(a), Val::Vw(b)) => Val::Vw(a.lerp(&b, t)),
+            (Val::Vh(a), Val::Vh(b)) => Val::Vh(a.lerp(&b, t)),
+            (Val::VMin(a), Val::VMin(b)) => Val::VMin(a.lerp(&b, t)),
+            (Val::VMax(a), Val::VMax(b)) => Val::VMax(a.lerp(&b, t)),
+            (from, to) => {
+                if t < 1.0 {
+                    from
+                } else {
+                    to
+                }
+            }
+        }
+    }
+}
+
+impl Lerp for UiRect {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        UiRect {
+            left: self.left.lerp(&to.left, t),
+            right: self.right.lerp(&to.right, t),
+            top: self.top.lerp(&to.top, t),
+            bottom: self.bottom.lerp(&to.bottom, t),
+        }
+    }
+}
@@ -0,0 +1,734 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bevy::ecs::system::Command;
+use bevy::ecs::world::EntityMut;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_mod_picking::prelude::EntityEvent;
+use dioxus::prelude::ScopeState;
+
+use crate::animation::{parse_duration_ms, Easing, Lerp};
+use crate::dioxus_ext::use_cmd_sender;
+use crate::{from_str, impl_self_event_data, EcsSender, MyFromStr};
+
+/// Values of one keyframe, unset values are not animated by it
+#[derive(Reflect, Debug, Default, Clone, PartialEq)]
+#[reflect(Default, PartialEq)]
+pub struct KeyframeValues {
+    pub background: Option<Color>,
+    pub border_color: Option<Color>,
+    pub rotation: Option<Quat>,
+    pub scale: Option<Vec3>,
+    pub width: Option<Val>,
+    pub height: Option<Val>,
+    pub left: Option<Val>,
+    pub right: Option<Val>,
+    pub top: Option<Val>,
+    pub bottom: Option<Val>,
+}
+
+macro_rules! keyframe_values_fields {
+    ($macro:ident) => {
+        $macro!(
+            background: Color,
+            border_color: Color,
+            rotation: Quat,
+            scale: Vec3,
+            width: Val,
+            height: Val,
+            left: Val,
+            right: Val,
+            top: Val,
+            bottom: Val
+        )
+    };
+}
+
+macro_rules! keyframe_values_builders {
+    ($($field:ident: $type:ty),*) => {
+        impl KeyframeValues {
+            $(
+                pub fn $field(mut self, value: $type) -> Self {
+                    self.$field = Some(value);
+                    self
+                }
+            )*
+        }
+    };
+}
+
+keyframe_values_fields!(keyframe_values_builders);
+
+impl KeyframeValues {
+    pub fn capture(entity_ref: &EntityMut) -> Self {
+        let transform = entity_ref.get::<Transform>();
+        let style = entity_ref.get::<Style>();
+        Self {
+            background: entity_ref.get::<BackgroundColor>().map(|n| n.0),
+            border_color: entity_ref.get::<BorderColor>().map(|n| n.0),
+            rotation: transform.map(|n| n.rotation),
+            scale: transform.map(|n| n.scale),
+            width: style.map(|n| n.width),
+            height: style.map(|n| n.height),
+            left: style.map(|n| n.left),
+            right: style.map(|n| n.right),
+            top: style.map(|n| n.top),
+            bottom: style.map(|n| n.bottom),
+        }
+    }
+
+    pub fn apply(&self, entity_ref: &mut EntityMut) {
+        if let Some(value) = self.background {
+            if let Some(mut background) = entity_ref.get_mut::<BackgroundColor>() {
+                background.0 = value;
+            }
+        }
+        if let Some(value) = self.border_color {
+            if let Some(mut border_color) = entity_ref.get_mut::<BorderColor>() {
+                border_color.0 = value;
+            }
+        }
+        if self.rotation.is_some() || self.scale.is_some() {
+            if let Some(mut transform) = entity_ref.get_mut::<Transform>() {
+                if let Some(value) = self.rotation {
+                    transform.rotation = value;
+                }
+                if let Some(value) = self.scale {
+                    transform.scale = value;
+                }
+            }
+        }
+        if let Some(mut style) = entity_ref.get_mut::<Style>() {
+            macro_rules! apply_vals {
+                ($($field:ident),*) => {
+                    $(
+                        if let Some(value) = self.$field {
+                            style.$field = value;
+                        }
+                    )*
+                };
+            }
+            apply_vals!(width, height, left, right, top, bottom);
+        }
+    }
+
+    /// Takes the `current` values set since `written`, for properties `self` has a value for
+    pub fn refresh_changed(&mut self, current: &KeyframeValues, written: &KeyframeValues) {
+        macro_rules! refresh {
+            ($($field:ident: $type:ty),*) => {
+                $(
+                    if let (Some(value), Some(current), Some(written)) =
+                        (&mut self.$field, &current.$field, &written.$field)
+                    {
+                        if current != written {
+                            *value = *current;
+                        }
+                    }
+                )*
+            };
+        }
+        keyframe_values_fields!(refresh)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Keyframe {
+    /// `0.0` is `from`, `1.0` is `to`
+    pub offset: f32,
+    pub values: KeyframeValues,
+}
+
+/// `@keyframes`-like definition, registered by name in [`UiKeyframes`]
+#[derive(Debug, Default, Clone)]
+pub struct Keyframes {
+    frames: Vec<Keyframe>,
+}
+
+impl Keyframes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn at(mut self, offset: f32, values: KeyframeValues) -> Self {
+        let offset = offset.clamp(0.0, 1.0);
+        let index = self.frames.partition_point(|n| n.offset <= offset);
+        self.frames.insert(index, Keyframe { offset, values });
+        self
+    }
+
+    pub fn from(self, values: KeyframeValues) -> Self {
+        self.at(0.0, values)
+    }
+
+    pub fn to(self, values: KeyframeValues) -> Self {
+        self.at(1.0, values)
+    }
+
+    /// Keeps the values of the properties some keyframe animates
+    pub fn retain_animated(&self, values: KeyframeValues) -> KeyframeValues {
+        macro_rules! retain {
+            ($($field:ident: $type:ty),*) => {
+                KeyframeValues {
+                    $(
+                        $field: values
+                            .$field
+                            .filter(|_| self.frames.iter().any(|n| n.values.$field.is_some())),
+                    )*
+                }
+            };
+        }
+        keyframe_values_fields!(retain)
+    }
+
+    fn sample_property<T: Lerp + Clone>(
+        &self,
+        progress: f32,
+        easing: Easing,
+        base: Option<T>,
+        get: impl Fn(&KeyframeValues) -> Option<T>,
+    ) -> Option<T> {
+        let mut points = self
+            .frames
+            .iter()
+            .filter_map(|n| Some((n.offset, get(&n.values)?)))
+            .collect::<Vec<_>>();
+        if points.is_empty() {
+            return None;
+        }
+        // like css, missing `from` / `to` frames use the value before the animation
+        if let Some(base) = base {
+            if points[0].0 > 0.0 {
+                points.insert(0, (0.0, base.clone()));
+            }
+            if points[points.len() - 1].0 < 1.0 {
+                points.push((1.0, base));
+            }
+        }
+        let index = points.partition_point(|n| n.0 <= progress);
+        if index == 0 {
+            return Some(points[0].1.clone());
+        }
+        if index == points.len() {
+            return Some(points[index - 1].1.clone());
+        }
+        let (from_offset, from) = &points[index - 1];
+        let (to_offset, to) = &points[index];
+        let t = (progress - from_offset) / (to_offset - from_offset);
+        Some(from.lerp(to, easing.ease(t)))
+    }
+
+    /// Values of the animated properties at `progress`, the easing applies to each keyframe interval
+    pub fn sample(&self, progress: f32, easing: Easing, base: &KeyframeValues) -> KeyframeValues {
+        macro_rules! sample {
+            ($($field:ident: $type:ty),*) => {
+                KeyframeValues {
+                    $(
+                        $field: self.sample_property(progress, easing, base.$field.clone(), |n| {
+                            n.$field.clone()
+                        }),
+                    )*
+                }
+            };
+        }
+        keyframe_values_fields!(sample)
+    }
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct UiKeyframes(pub HashMap<String, Keyframes>);
+
+impl UiKeyframes {
+    pub fn add(&mut self, name: impl Into<String>, keyframes: Keyframes) -> &mut Self {
+        self.0.insert(name.into(), keyframes);
+        self
+    }
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub enum AnimationIterations {
+    Count(f32),
+    Infinite,
+}
+
+impl Default for AnimationIterations {
+    fn default() -> Self {
+        AnimationIterations::Count(1.0)
+    }
+}
+
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationDirection {
+    #[default]
+    Normal,
+    Reverse,
+    Alternate,
+    AlternateReverse,
+}
+
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationFillMode {
+    #[default]
+    None,
+    Forwards,
+    Backwards,
+    Both,
+}
+
+impl AnimationFillMode {
+    fn forwards(self) -> bool {
+        matches!(self, AnimationFillMode::Forwards | AnimationFillMode::Both)
+    }
+
+    fn backwards(self) -> bool {
+        matches!(self, AnimationFillMode::Backwards | AnimationFillMode::Both)
+    }
+}
+
+/// Value of the `animation` attr, `"spin 1s linear infinite"`
+#[derive(Reflect, Debug, Clone, PartialEq)]
+#[reflect(Default, PartialEq)]
+pub struct AnimationSpec {
+    /// name in [`UiKeyframes`], empty for no animation
+    pub name: String,
+    /// in seconds
+    pub duration: f32,
+    /// in seconds
+    pub delay: f32,
+    pub easing: Easing,
+    pub iterations: AnimationIterations,
+    pub direction: AnimationDirection,
+    pub fill_mode: AnimationFillMode,
+}
+
+impl Default for AnimationSpec {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            duration: 1.0,
+            delay: 0.0,
+            easing: Easing::Ease,
+            iterations: default(),
+            direction: default(),
+            fill_mode: default(),
+        }
+    }
+}
+
+impl MyFromStr for AnimationSpec {
+    fn from_str(s: &str) -> Option<Self> {
+        let mut r = Self::default();
+        let mut has_duration = false;
+        for part in s.split_whitespace() {
+            if let Some(ms) = parse_duration_ms(part) {
+                if has_duration {
+                    r.delay = ms / 1000.0;
+                } else {
+                    r.duration = ms / 1000.0;
+                    has_duration = true;
+                }
+            } else if let Some(easing) = from_str::<Easing>(part) {
+                r.easing = easing;
+            } else if part == "infinite" {
+                r.iterations = AnimationIterations::Infinite;
+            } else if let Ok(count) = part.parse::<f32>() {
+                r.iterations = AnimationIterations::Count(count);
+            } else {
+                match part {
+                    "normal" => r.direction = AnimationDirection::Normal,
+                    "reverse" => r.direction = AnimationDirection::Reverse,
+                    "alternate" => r.direction = AnimationDirection::Alternate,
+                    "alternate-reverse" => r.direction = AnimationDirection::AlternateReverse,
+                    "none" => r.fill_mode = AnimationFillMode::None,
+                    "forwards" => r.fill_mode = AnimationFillMode::Forwards,
+                    "backwards" => r.fill_mode = AnimationFillMode::Backwards,
+                    "both" => r.fill_mode = AnimationFillMode::Both,
+                    name => r.name = name.to_string(),
+                }
+            }
+        }
+        Some(r)
+    }
+}
+
+impl AnimationSpec {
+    fn total_duration(&self) -> f32 {
+        match self.iterations {
+            AnimationIterations::Count(count) => self.duration * count.max(0.0),
+            AnimationIterations::Infinite => f32::INFINITY,
+        }
+    }
+
+    fn directed_progress(&self, iteration: u32, progress: f32) -> f32 {
+        let reversed = match self.direction {
+            AnimationDirection::Normal => false,
+            AnimationDirection::Reverse => true,
+            AnimationDirection::Alternate => iteration % 2 == 1,
+            AnimationDirection::AlternateReverse => iteration % 2 == 0,
+        };
+        if reversed {
+            1.0 - progress
+        } else {
+            progress
+        }
+    }
+
+    /// Keyframe progress at `elapsed`, `None` when the animation has no effect
+    pub fn progress(&self, elapsed: f32) -> Option<f32> {
+        let active_time = elapsed - self.delay;
+        if active_time < 0.0 {
+            return self
+                .fill_mode
+                .backwards()
+                .then(|| self.directed_progress(0, 0.0));
+        }
+        let total = self.total_duration();
+        if active_time >= total {
+            if !self.fill_mode.forwards() {
+                return None;
+            }
+            let iterations = total / self.duration.max(f32::EPSILON);
+            let (iteration, progress) = if iterations.fract() == 0.0 && iterations > 0.0 {
+                (iterations as u32 - 1, 1.0)
+            } else {
+                (iterations as u32, iterations.fract())
+            };
+            return Some(self.directed_progress(iteration, progress));
+        }
+        if self.duration <= 0.0 {
+            return Some(self.directed_progress(0, 1.0));
+        }
+        let iterations = active_time / self.duration;
+        Some(self.directed_progress(iterations as u32, iterations.fract()))
+    }
+
+    pub fn is_finished(&self, elapsed: f32) -> bool {
+        elapsed - self.delay >= self.total_duration()
+    }
+}
+
+/// Running keyframe animation, inserted by the `animation` attr
+#[derive(Component, Reflect, Debug, Default, Clone, PartialEq)]
+#[reflect(Component, Default)]
+pub struct UiAnimation {
+    pub spec: AnimationSpec,
+    /// in seconds, including the delay
+    pub elapsed: f32,
+    pub paused: bool,
+    pub finished: bool,
+}
+
+impl UiAnimation {
+    pub fn new(spec: AnimationSpec) -> Self {
+        Self {
+            spec,
+            ..default()
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.finished && !self.paused
+    }
+}
+
+/// Values of the animated properties without the animation, restored when it has no effect.
+/// Values set by attrs or classes while it runs replace the ones captured when it started.
+#[derive(Component, Debug, Clone)]
+pub struct AnimationBase {
+    pub values: KeyframeValues,
+    /// values the animation wrote last, other values were set by something else
+    pub written: Option<KeyframeValues>,
+}
+
+impl AnimationBase {
+    pub fn refresh(&mut self, current: &KeyframeValues) {
+        if let Some(written) = &self.written {
+            self.values.refresh_changed(current, written);
+        }
+    }
+}
+
+#[derive(Event, Clone, Debug, PartialEq, Reflect)]
+pub struct AnimationEndEvent {
+    pub target: Entity,
+    pub name: String,
+    /// in seconds
+    pub elapsed: f32,
+}
+
+impl EntityEvent for AnimationEndEvent {
+    fn target(&self) -> Entity {
+        self.target
+    }
+}
+
+impl_self_event_data!(AnimationEndEvent);
+
+pub fn restore_animation_base(entity_ref: &mut EntityMut) {
+    if let Some(mut base) = entity_ref.take::<AnimationBase>() {
+        base.refresh(&KeyframeValues::capture(entity_ref));
+        base.values.apply(entity_ref);
+    }
+}
+
+pub fn update_animations(world: &mut World) {
+    let delta = world.resource::<Time>().delta_seconds();
+    let entities = world
+        .query_filtered::<Entity, With<UiAnimation>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    world.resource_scope(|world, keyframes: Mut<UiKeyframes>| {
+        for entity in entities {
+            let mut entity_ref = world.entity_mut(entity);
+            let mut animation = entity_ref.get::<UiAnimation>().unwrap().clone();
+            if animation.finished && !animation.paused {
+                continue;
+            }
+            let Some(frames) = keyframes.get(&animation.spec.name) else {
+                continue;
+            };
+            if !animation.paused {
+                animation.elapsed += delta;
+            }
+            let current = KeyframeValues::capture(&entity_ref);
+            // updated in place, inserting every frame would move the entity between archetypes
+            if let Some(mut base) = entity_ref.get_mut::<AnimationBase>() {
+                base.bypass_change_detection().refresh(&current);
+            } else {
+                entity_ref.insert(AnimationBase {
+                    values: frames.retain_animated(current),
+                    written: None,
+                });
+            }
+            let base = entity_ref.get::<AnimationBase>().unwrap();
+            let written = match animation.spec.progress(animation.elapsed) {
+                Some(progress) => frames.sample(progress, animation.spec.easing, &base.values),
+                None => base.values.clone(),
+            };
+            written.apply(&mut entity_ref);
+            let finished = animation.spec.is_finished(animation.elapsed);
+            let end_event = (finished && !animation.finished).then(|| AnimationEndEvent {
+                target: entity,
+                name: animation.spec.name.clone(),
+                elapsed: animation.elapsed,
+            });
+            animation.finished = finished;
+            if !finished || animation.spec.fill_mode.forwards() {
+                let mut base = entity_ref.get_mut::<AnimationBase>().unwrap();
+                base.bypass_change_detection().written = Some(written);
+            } else {
+                entity_ref.remove::<AnimationBase>();
+            }
+            entity_ref
+                .get_mut::<UiAnimation>()
+                .unwrap()
+                .set_if_neq(animation);
+            if let Some(end_event) = end_event {
+                world.send_event(end_event);
+            }
+        }
+    });
+}
+
+/// Links an element to a [`UseAnimation`], set by the `animation_handle` attr
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Component, Default)]
+pub struct AnimationHandle(pub u64);
+
+impl AnimationHandle {
+    pub fn new() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl<'a> dioxus::prelude::IntoAttributeValue<'a> for AnimationHandle {
+    fn into_value(
+        self,
+        bump: &'a dioxus::core::exports::bumpalo::Bump,
+    ) -> dioxus::core::AttributeValue<'a> {
+        crate::Attr(self).into_value(bump)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnimationAction {
+    Play,
+    Pause,
+    Restart,
+    /// in seconds, including the delay
+    Seek(f32),
+}
+
+pub struct ControlAnimation {
+    pub handle: AnimationHandle,
+    pub action: AnimationAction,
+}
+
+impl Command for ControlAnimation {
+    fn apply(self, world: &mut World) {
+        let mut query = world.query::<(&AnimationHandle, &mut UiAnimation)>();
+        for (handle, mut animation) in query.iter_mut(world) {
+            if *handle != self.handle {
+                continue;
+            }
+            match self.action {
+                AnimationAction::Play => animation.paused = false,
+                AnimationAction::Pause => animation.paused = true,
+                AnimationAction::Restart => {
+                    animation.elapsed = 0.0;
+                    animation.paused = false;
+                    animation.finished = false;
+                }
+                AnimationAction::Seek(elapsed) => {
+                    animation.elapsed = elapsed.max(0.0);
+                    animation.finished = false;
+                }
+            }
+        }
+    }
+}
+
+/// Controls the animation of the elements given `animation_handle: animation.handle()`
+pub struct UseAnimation {
+    handle: AnimationHandle,
+    sender: EcsSender,
+}
+
+impl UseAnimation {
+    pub fn handle(&self) -> AnimationHandle {
+        self.handle
+    }
+
+    fn send(&self, action: AnimationAction) {
+        self.sender.send_cmd(ControlAnimation {
+            handle: self.handle,
+            action,
+        });
+    }
+
+    pub fn play(&self) {
+        self.send(AnimationAction::Play);
+    }
+
+    pub fn pause(&self) {
+        self.send(AnimationAction::Pause);
+    }
+
+    pub fn restart(&self) {
+        self.send(AnimationAction::Restart);
+    }
+
+    /// `elapsed` in seconds, including the delay
+    pub fn seek(&self, elapsed: f32) {
+        self.send(AnimationAction::Seek(elapsed));
+    }
+}
+
+pub fn use_animation(cx: &ScopeState) -> &UseAnimation {
+    let sender = use_cmd_sender(cx);
+    cx.use_hook(|| UseAnimation {
+        handle: AnimationHandle::new(),
+        sender,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(s: &str) -> AnimationSpec {
+        from_str(s).unwrap()
+    }
+
+    #[test]
+    fn parse_spec() {
+        let r = spec("spin 1s linear infinite");
+        assert_eq!(r.name, "spin");
+        assert_eq!(r.duration, 1.0);
+        assert_eq!(r.easing, Easing::Linear);
+        assert_eq!(r.iterations, AnimationIterations::Infinite);
+
+        let r = spec("fade 300ms 100ms 2 alternate-reverse both");
+        assert_eq!(r.name, "fade");
+        assert!((r.duration - 0.3).abs() < 1e-6);
+        assert!((r.delay - 0.1).abs() < 1e-6);
+        assert_eq!(r.iterations, AnimationIterations::Count(2.0));
+        assert_eq!(r.direction, AnimationDirection::AlternateReverse);
+        assert_eq!(r.fill_mode, AnimationFillMode::Both);
+
+        assert_eq!(spec(""), AnimationSpec::default());
+    }
+
+    #[test]
+    fn progress() {
+        let r = spec("a 1s");
+        assert_eq!(r.progress(0.25), Some(0.25));
+        assert_eq!(r.progress(1.0), None);
+        assert!(r.is_finished(1.0));
+        assert!(!r.is_finished(0.5));
+
+        let r = spec("a 1s 2");
+        assert_eq!(r.progress(1.25), Some(0.25));
+        assert_eq!(r.progress(2.0), None);
+
+        let r = spec("a 1s infinite");
+        assert_eq!(r.progress(10.5), Some(0.5));
+        assert!(!r.is_finished(1000.0));
+    }
+
+    #[test]
+    fn progress_direction() {
+        assert_eq!(spec("a 1s reverse").progress(0.25), Some(0.75));
+        let r = spec("a 1s 2 alternate");
+        assert_eq!(r.progress(0.25), Some(0.25));
+        assert_eq!(r.progress(1.25), Some(0.75));
+        let r = spec("a 1s 2 alternate-reverse");
+        assert_eq!(r.progress(0.25), Some(0.75));
+        assert_eq!(r.progress(1.25), Some(0.25));
+    }
+
+    #[test]
+    fn progress_fill_mode() {
+        // before the delay
+        assert_eq!(spec("a 1s 500ms").progress(0.2), None);
+        assert_eq!(spec("a 1s 500ms backwards").progress(0.2), Some(0.0));
+        assert_eq!(spec("a 1s 500ms reverse both").progress(0.2), Some(1.0));
+        assert_eq!(spec("a 1s 500ms forwards").progress(0.2), None);
+        // after the end
+        assert_eq!(spec("a 1s forwards").progress(2.0), Some(1.0));
+        assert_eq!(spec("a 1s backwards").progress(2.0), None);
+        assert_eq!(spec("a 1s 2 alternate forwards").progress(3.0), Some(0.0));
+        assert_eq!(spec("a 1s 1.5 both").progress(3.0), Some(0.5));
+    }
+
+    #[test]
+    fn sample_uses_base_for_missing_frames() {
+        let frames = Keyframes::new().at(0.5, KeyframeValues::default().width(Val::Px(100.)));
+        let base = KeyframeValues::default().width(Val::Px(0.)).height(Val::Px(10.));
+        let values = frames.sample(0.25, Easing::Linear, &base);
+        assert_eq!(values.width, Some(Val::Px(50.)));
+        assert_eq!(values.height, None);
+        assert_eq!(frames.sample(1.0, Easing::Linear, &base).width, Some(Val::Px(0.)));
+    }
+
+    #[test]
+    fn base_keeps_animated_and_refreshed_values() {
+        let frames = Keyframes::new().to(KeyframeValues::default().background(Color::RED));
+        let captured = KeyframeValues::default()
+            .background(Color::WHITE)
+            .width(Val::Px(10.));
+        let mut base = frames.retain_animated(captured);
+        assert_eq!(base, KeyframeValues::default().background(Color::WHITE));
+
+        let written = KeyframeValues::default().background(Color::PINK);
+        // unchanged since the animation wrote it
+        base.refresh_changed(&written, &written);
+        assert_eq!(base.background, Some(Color::WHITE));
+        // set by a class while running
+        let current = KeyframeValues::default()
+            .background(Color::BLUE)
+            .width(Val::Px(20.));
+        base.refresh_changed(&current, &written);
+        assert_eq!(base, KeyframeValues::default().background(Color::BLUE));
+    }
+}
@@ -0,0 +1,7 @@
+pub use easing::*;
+pub use keyframes::*;
+pub use transition::*;
+
+mod easing;
+mod keyframes;
+mod transition;
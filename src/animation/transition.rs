@@ -0,0 +1,448 @@
+use std::ops::BitOr;
+
+use bevy::ecs::world::EntityMut;
+use bevy::prelude::*;
+
+use crate::animation::{Easing, Lerp, UiAnimation};
+use crate::{from_str, MyFromStr};
+
+/// Groups of attrs animated by the `transition` attr
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Default, PartialEq)]
+pub struct TransitionProperties(u8);
+
+impl TransitionProperties {
+    pub const NONE: Self = Self(0);
+    /// `background`, `border_color`, `text_color`
+    pub const COLORS: Self = Self(1 << 0);
+    /// `rotation`, `scale`. Translation isn't animated, the layout sets it every frame.
+    pub const TRANSFORM: Self = Self(1 << 1);
+    /// sizes, positions, margins, paddings, borders and gaps of `Style`, `font_size`
+    pub const LAYOUT: Self = Self(1 << 2);
+    pub const ALL: Self = Self(0b111);
+
+    #[inline]
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "none" => Self::NONE,
+            "all" => Self::ALL,
+            "colors" | "background" | "border_color" | "text_color" => Self::COLORS,
+            "transform" | "rotation" | "scale" => Self::TRANSFORM,
+            "layout" | "width" | "height" | "min_width" | "min_height" | "max_width"
+            | "max_height" | "left" | "right" | "top" | "bottom" | "margin" | "padding"
+            | "border" | "column_gap" | "row_gap" | "flex_grow" | "flex_shrink" | "flex_basis"
+            | "font_size" => {
+                Self::LAYOUT
+            }
+            _ => return None,
+        })
+    }
+}
+
+impl BitOr for TransitionProperties {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl MyFromStr for TransitionProperties {
+    /// `"colors, transform"`
+    fn from_str(s: &str) -> Option<Self> {
+        s.split([',', ' '])
+            .filter(|n| !n.is_empty())
+            .try_fold(Self::NONE, |r, name| Some(r | Self::from_name(name)?))
+    }
+}
+
+/// Value of the `transition` attr, only the given parts are changed.
+/// `"colors 300ms ease-out 50ms"`: properties, duration, timing function and delay.
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq)]
+#[reflect(Default, PartialEq)]
+pub struct TransitionShorthand {
+    pub properties: Option<TransitionProperties>,
+    pub duration: Option<f32>,
+    pub easing: Option<Easing>,
+    pub delay: Option<f32>,
+}
+
+/// `300ms`, `0.3s`, returned in milliseconds
+pub fn parse_duration_ms(s: &str) -> Option<f32> {
+    if let Some(ms) = s.strip_suffix("ms") {
+        ms.parse().ok()
+    } else if let Some(secs) = s.strip_suffix('s') {
+        secs.parse::<f32>().ok().map(|n| n * 1000.0)
+    } else {
+        None
+    }
+}
+
+impl MyFromStr for TransitionShorthand {
+    fn from_str(s: &str) -> Option<Self> {
+        let mut r = Self::default();
+        let mut properties = TransitionProperties::NONE;
+        let mut has_properties = false;
+        for part in s.split_whitespace() {
+            if let Some(ms) = parse_duration_ms(part) {
+                if r.duration.is_none() {
+                    r.duration = Some(ms);
+                } else {
+                    r.delay = Some(ms);
+                }
+            } else if let Some(easing) = from_str::<Easing>(part) {
+                r.easing = Some(easing);
+            } else {
+                properties = properties | from_str::<TransitionProperties>(part)?;
+                has_properties = true;
+            }
+        }
+        if has_properties {
+            r.properties = Some(properties);
+        }
+        Some(r)
+    }
+}
+
+/// Animates attr changes of the element, see [`TransitionProperties`]
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component, Default)]
+pub struct UiTransition {
+    pub properties: TransitionProperties,
+    /// in seconds
+    pub duration: f32,
+    /// in seconds
+    pub delay: f32,
+    pub easing: Easing,
+}
+
+impl UiTransition {
+    pub const DEFAULT_DURATION_MS: f32 = 150.0;
+}
+
+impl Default for UiTransition {
+    fn default() -> Self {
+        Self {
+            properties: TransitionProperties::NONE,
+            duration: Self::DEFAULT_DURATION_MS / 1000.0,
+            delay: 0.0,
+            easing: default(),
+        }
+    }
+}
+
+/// Inserts [`TransitionTracks`] together with the transition, so they start from the values
+/// of the current frame and the first change after spawning is animated
+pub fn update_transition(entity_ref: &mut EntityMut, f: impl FnOnce(&mut UiTransition)) {
+    if let Some(mut transition) = entity_ref.get_mut::<UiTransition>() {
+        f(&mut transition);
+    } else {
+        let mut transition = UiTransition::default();
+        f(&mut transition);
+        entity_ref.insert((transition, TransitionTracks::default()));
+    }
+}
+
+/// `Style` fields animated by [`TransitionProperties::LAYOUT`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutValues {
+    pub width: Val,
+    pub height: Val,
+    pub min_width: Val,
+    pub min_height: Val,
+    pub max_width: Val,
+    pub max_height: Val,
+    pub left: Val,
+    pub right: Val,
+    pub top: Val,
+    pub bottom: Val,
+    pub margin: UiRect,
+    pub padding: UiRect,
+    pub border: UiRect,
+    pub column_gap: Val,
+    pub row_gap: Val,
+    pub flex_grow: f32,
+    pub flex_shrink: f32,
+    pub flex_basis: Val,
+}
+
+macro_rules! layout_values_fields {
+    ($macro:ident) => {
+        $macro!(
+            width, height, min_width, min_height, max_width, max_height, left, right, top, bottom,
+            margin, padding, border, column_gap, row_gap, flex_grow, flex_shrink, flex_basis
+        )
+    };
+}
+
+impl LayoutValues {
+    pub fn from_style(style: &Style) -> Self {
+        macro_rules! from_style {
+            ($($field:ident),*) => {
+                Self { $($field: style.$field.clone(),)* }
+            };
+        }
+        layout_values_fields!(from_style)
+    }
+
+    pub fn apply_to(&self, style: &mut Style) {
+        macro_rules! apply_to {
+            ($($field:ident),*) => {
+                $(style.$field = self.$field.clone();)*
+            };
+        }
+        layout_values_fields!(apply_to)
+    }
+}
+
+impl Lerp for LayoutValues {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        macro_rules! lerp {
+            ($($field:ident),*) => {
+                Self { $($field: self.$field.lerp(&to.$field, t),)* }
+            };
+        }
+        layout_values_fields!(lerp)
+    }
+}
+
+/// `Transform` parts animated by [`TransitionProperties::TRANSFORM`], translation is owned by the layout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformValues {
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl Lerp for TransformValues {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Self {
+            rotation: self.rotation.lerp(&to.rotation, t),
+            scale: self.scale.lerp(&to.scale, t),
+        }
+    }
+}
+
+/// Interpolates from the displayed value to a new one whenever the component value is changed
+/// by something else than the transition itself.
+#[derive(Debug, Clone)]
+pub struct TransitionTrack<T> {
+    from: T,
+    to: T,
+    written: T,
+    elapsed: f32,
+}
+
+impl<T: Lerp + PartialEq + Clone> TransitionTrack<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            from: value.clone(),
+            to: value.clone(),
+            written: value,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Returns the value to write back when the track is running
+    pub fn step(&mut self, current: &T, transition: &UiTransition, delta: f32) -> Option<T> {
+        if *current != self.written {
+            self.from = self.written.clone();
+            self.to = current.clone();
+            self.elapsed = -transition.delay;
+        }
+        if self.written == self.to {
+            return None;
+        }
+        self.elapsed += delta;
+        let t = if transition.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / transition.duration).clamp(0.0, 1.0)
+        };
+        self.written = if t >= 1.0 {
+            self.to.clone()
+        } else {
+            self.from.lerp(&self.to, transition.easing.ease(t))
+        };
+        Some(self.written.clone())
+    }
+}
+
+#[derive(Component, Default)]
+pub struct TransitionTracks {
+    background: Option<TransitionTrack<Color>>,
+    border_color: Option<TransitionTrack<Color>>,
+    transform: Option<TransitionTrack<TransformValues>>,
+    layout: Option<TransitionTrack<LayoutValues>>,
+    text_color: Option<TransitionTrack<Color>>,
+    font_size: Option<TransitionTrack<f32>>,
+}
+
+fn step_track<T: Lerp + PartialEq + Clone>(
+    track: &mut Option<TransitionTrack<T>>,
+    enabled: bool,
+    current: T,
+    transition: &UiTransition,
+    delta: f32,
+) -> Option<T> {
+    if !enabled {
+        *track = None;
+        return None;
+    }
+    match track {
+        Some(track) => track.step(&current, transition, delta),
+        None => {
+            *track = Some(TransitionTrack::new(current));
+            None
+        }
+    }
+}
+
+pub fn update_transitions(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut removed: RemovedComponents<UiTransition>,
+    mut query: Query<(
+        Entity,
+        &UiTransition,
+        Option<&mut TransitionTracks>,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+        Option<&mut Transform>,
+        Option<&mut Style>,
+        Option<&mut Text>,
+        Option<&UiAnimation>,
+    )>,
+) {
+    for entity in removed.iter() {
+        if let Some(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.remove::<TransitionTracks>();
+        }
+    }
+    let delta = time.delta_seconds();
+    for (entity, transition, tracks, background, border_color, transform, style, text, animation) in
+        query.iter_mut()
+    {
+        let mut inserted = None;
+        // `UiTransition` inserted by apps or cloned from templates, the tracks start from the
+        // current values below and are inserted afterwards
+        let tracks = match tracks {
+            Some(tracks) => tracks.into_inner(),
+            None => inserted.insert(TransitionTracks::default()),
+        };
+        // a running keyframe animation owns the values, tracks restart from where it leaves them
+        if animation.is_some_and(|n| !n.finished) {
+            *tracks = TransitionTracks::default();
+        } else {
+            let values = TrackedValues {
+                background,
+                border_color,
+                transform,
+                style,
+                text,
+            };
+            step_tracks(tracks, transition, delta, values);
+        }
+        if let Some(tracks) = inserted {
+            commands.entity(entity).insert(tracks);
+        }
+    }
+}
+
+/// Components of an element the tracks read and write
+struct TrackedValues<'w> {
+    background: Option<Mut<'w, BackgroundColor>>,
+    border_color: Option<Mut<'w, BorderColor>>,
+    transform: Option<Mut<'w, Transform>>,
+    style: Option<Mut<'w, Style>>,
+    /// every section takes the color and size of the first one, like the `text_color` and
+    /// `font_size` attrs set them
+    text: Option<Mut<'w, Text>>,
+}
+
+fn step_tracks(
+    tracks: &mut TransitionTracks,
+    transition: &UiTransition,
+    delta: f32,
+    values: TrackedValues,
+) {
+    let TrackedValues {
+        background,
+        border_color,
+        transform,
+        style,
+        text,
+    } = values;
+    let properties = transition.properties;
+    let colors = properties.contains(TransitionProperties::COLORS);
+    if let Some(mut background) = background {
+        let current = background.0;
+        if let Some(value) =
+            step_track(&mut tracks.background, colors, current, transition, delta)
+        {
+            background.0 = value;
+        }
+    }
+    if let Some(mut border_color) = border_color {
+        let current = border_color.0;
+        if let Some(value) =
+            step_track(&mut tracks.border_color, colors, current, transition, delta)
+        {
+            border_color.0 = value;
+        }
+    }
+    if let Some(mut transform) = transform {
+        let current = TransformValues {
+            rotation: transform.rotation,
+            scale: transform.scale,
+        };
+        let enabled = properties.contains(TransitionProperties::TRANSFORM);
+        if let Some(value) =
+            step_track(&mut tracks.transform, enabled, current, transition, delta)
+        {
+            transform.rotation = value.rotation;
+            transform.scale = value.scale;
+        }
+    }
+    if let Some(mut style) = style {
+        let current = LayoutValues::from_style(&style);
+        let enabled = properties.contains(TransitionProperties::LAYOUT);
+        if let Some(value) =
+            step_track(&mut tracks.layout, enabled, current, transition, delta)
+        {
+            value.apply_to(&mut style);
+        }
+    }
+    if let Some(mut text) = text {
+        let Some((color, font_size)) = text
+            .sections
+            .first()
+            .map(|n| (n.style.color, n.style.font_size))
+        else {
+            return;
+        };
+        if let Some(value) =
+            step_track(&mut tracks.text_color, colors, color, transition, delta)
+        {
+            for section in text.sections.iter_mut() {
+                section.style.color = value;
+            }
+        }
+        let enabled = properties.contains(TransitionProperties::LAYOUT);
+        if let Some(value) =
+            step_track(&mut tracks.font_size, enabled, font_size, transition, delta)
+        {
+            for section in text.sections.iter_mut() {
+                section.style.font_size = value;
+            }
+        }
+    }
+}
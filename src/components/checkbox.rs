@@ -40,6 +40,7 @@ pub fn Checkbox<'a>(cx: Scope<'a, CheckboxProps<'a>>) -> Element<'a> {
     render! {
         view {
             class: "border border-gray-300 bg-white w-5 h-5 justify-center items-center {cx.props.class}",
+            checked: **is_checked,
             onclick: handle_click,
             ..cx.props.attributes,
             // if **is_checked {"X"} else {" "} todo:
@@ -3,12 +3,14 @@ use std::rc::Rc;
 pub use checkbox::*;
 pub use selectable_list::*;
 pub use dropdown::*;
+pub use text_input::*;
 
 use crate::prelude::*;
 
 mod checkbox;
 mod selectable_list;
 mod dropdown;
+mod text_input;
 
 pub fn use_state_change<T: PartialEq + 'static>(
     cx: &ScopeState,
@@ -0,0 +1,57 @@
+use std::rc::Rc;
+
+use crate::{components::use_controlled_state, prelude::*};
+
+#[derive(Props)]
+pub struct TextInputProps<'a> {
+    #[props(default = "")]
+    class: &'a str,
+    value: Option<String>,
+    #[props(default)]
+    initial_value: String,
+    oninput: Option<EventHandler<'a, String>>,
+    onchange: Option<EventHandler<'a, String>>,
+    onsubmit: Option<EventHandler<'a, String>>,
+    #[props(extends = InputAttrs)]
+    attributes: Vec<Attribute<'a>>,
+}
+
+#[component]
+pub fn TextInput<'a>(cx: Scope<'a, TextInputProps<'a>>) -> Element<'a> {
+    let value = cx.props.value.clone();
+    let text = use_controlled_state(
+        cx,
+        value.clone(),
+        move || value.unwrap_or_else(|| cx.props.initial_value.clone()),
+        |_: Rc<String>| {},
+    );
+    let revision = use_state(cx, || 0u64);
+    let handle_input = move |e: Event<UiEvent<TextInputEvent>>| {
+        revision.set(e.data.revision);
+        text.set(e.data.value.clone());
+        if let Some(oninput) = &cx.props.oninput {
+            oninput.call(e.data.value.clone());
+        }
+    };
+    let handle_change = move |e: Event<UiEvent<TextChangeEvent>>| {
+        if let Some(onchange) = &cx.props.onchange {
+            onchange.call(e.data.value.clone());
+        }
+    };
+    let handle_submit = move |e: Event<UiEvent<TextSubmitEvent>>| {
+        if let Some(onsubmit) = &cx.props.onsubmit {
+            onsubmit.call(e.data.value.clone());
+        }
+    };
+    render! {
+        input {
+            class: "{cx.props.class}",
+            value_revision: "{revision}",
+            text_value: "{text}",
+            oninput: handle_input,
+            onchange: handle_change,
+            onsubmit: handle_submit,
+            ..cx.props.attributes,
+        }
+    }
+}
@@ -5,21 +5,47 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 use bevy::ecs::system::{Command, CommandQueue, SystemBuffer, SystemMeta};
 use bevy::prelude::*;
+use bevy::transform::TransformSystem;
 use bevy::ui::widget::TextFlags;
+use bevy::ui::UiSystem;
+use bevy::window::PrimaryWindow;
 use bevy_cosmic_edit::{CosmicEditPlugin, CosmicText, Focus, ReadOnly};
 use bevy_mod_picking::DefaultPickingPlugins;
-use bevy_mod_picking::prelude::PickingInteraction;
+use bevy_mod_picking::prelude::{PickSet, PickingInteraction};
 use dioxus::core::ElementId;
 use dioxus::prelude::*;
 
-use crate::{BevyDioxusAppExt, SetAttrValueContext, TextSections, UiTexture};
+use crate::{
+    AttachedComponents, BevyDioxusAppExt, CosmicFontFamilies, ElementRegistry, InputFont,
+    InputOptions, InputState, ScrollView, SetAttrValueContext, TextSections, UiComponents,
+    UiTexture, UiTranslation,
+};
+use crate::animation::{
+    update_animations, update_transitions, AnimationEndEvent, AnimationHandle, UiAnimation,
+    UiKeyframes, UiTransition,
+};
 use crate::apc::{self};
+use crate::drag_drop::{end_drags, spawn_drag_ghosts, update_drag_ghosts, Draggable};
 use crate::ecs_apc::{EcsApcReceiver, EcsApcSender};
-use crate::entity_extra_data::EntitiesExtraData;
+use crate::entity_extra_data::{Checked, Disabled, EntitiesExtraData, UiGroup};
+use crate::prelude::dioxus_elements::events::add_dom_event_systems;
 use crate::prelude::{Click, ListenerInput, On, Pointer};
-use crate::tailwind::{handle_interaction_classes, InteractionClass};
+use crate::stylesheet::{apply_stylesheets, UiStyleSheet, UiStyleSheetLoader, UiStyleSheets};
+use crate::tailwind::{
+    apply_tailwind_theme, breakpoint_states, reapply_classes, sync_tailwind_theme_asset,
+    update_element_states, InteractionClass, TailwindClassCache, TailwindDiagnostics,
+    TailwindTheme, TailwindThemeLoader, UiColorScheme, UiViewportWidth, UnknownClassWarning,
+};
+use crate::ui_events::{
+    count_pointer_clicks, dispatch_wheel_events, DoubleClick, PointerClickCounter, ScrollEvent,
+    TextChangeEvent, TextInputEvent, TextSubmitEvent, WheelEvent,
+};
 use crate::vdm_data::{TemplateData, VDomData};
 use crate::vdom_main::{EcsMsg, vdom_main};
+use crate::elements::{
+    dispatch_input_commit_events, dispatch_input_text_events, update_input_fonts,
+    update_input_rows_height, update_input_text_layout, update_scroll_view_layout,
+};
 
 #[derive(Component)]
 pub struct NodeTemplate;
@@ -93,6 +119,7 @@ impl Plugin for DioxusPlugin {
         let (dom_apc_sender, dom_apc_receiver) = apc::channel();
 
         let is_dioxus_rendered: Arc<AtomicBool> = Arc::new(false.into());
+        let class_cache = TailwindClassCache::default();
 
         let vdom_data = {
             let mut r = VDomData::new();
@@ -118,17 +145,58 @@ impl Plugin for DioxusPlugin {
             });
             r
         };
-        app.add_plugins((DefaultPickingPlugins, CosmicEditPlugin::default()))
+        app.add_plugins((
+            DefaultPickingPlugins,
+            CosmicEditPlugin::default(),
+        ))
+            .add_event::<WheelEvent>()
+            .add_event::<ScrollEvent>()
+            .add_event::<TextInputEvent>()
+            .add_event::<TextChangeEvent>()
+            .add_event::<TextSubmitEvent>()
+            .add_event::<AnimationEndEvent>()
+            .add_event::<UnknownClassWarning>()
+            .add_event::<Pointer<DoubleClick>>()
+            .init_resource::<PointerClickCounter>()
             .register_type::<TextFlags>()
             .register_type::<PickingInteraction>()
             .register_type::<InteractionClass>()
             .register_type::<TextSections>()
             .register_type::<UiTexture>()
+            .register_type::<ScrollView>()
+            .register_type::<InputState>()
+            .register_type::<InputOptions>()
+            .register_type::<InputFont>()
+            .register_type::<Draggable>()
+            .register_type::<Disabled>()
+            .register_type::<Checked>()
+            .register_type::<UiGroup>()
+            .register_type::<UiComponents>()
+            .register_type::<AttachedComponents>()
+            .register_type::<UiTranslation>()
+            .register_type::<UiTransition>()
+            .register_type::<UiAnimation>()
+            .register_type::<AnimationHandle>()
+            .init_resource::<UiKeyframes>()
+            .add_asset::<TailwindTheme>()
+            .init_asset_loader::<TailwindThemeLoader>()
+            .init_resource::<TailwindTheme>()
+            .init_resource::<TailwindDiagnostics>()
+            .add_asset::<UiStyleSheet>()
+            .init_asset_loader::<UiStyleSheetLoader>()
+            .init_resource::<UiStyleSheets>()
+            .init_resource::<UiViewportWidth>()
+            .init_resource::<UiColorScheme>()
+            .register_type::<UiColorScheme>()
+            .init_resource::<CosmicFontFamilies>()
             .register_elements_type()
+            .init_resource::<ElementRegistry>()
+            .insert_resource(class_cache.clone())
             .insert_resource({
                 let mut world = World::default();
                 world.insert_resource(TemplateData::default());
                 world.insert_resource(EntitiesExtraData::default());
+                world.insert_resource(class_cache);
                 TemplateWorld(world)
             })
             .insert_resource(EntitiesExtraData::default())
@@ -141,11 +209,46 @@ impl Plugin for DioxusPlugin {
                 Update,
                 (
                     focus_input,
+                    dispatch_input_text_events,
+                    dispatch_input_commit_events,
+                    update_input_fonts,
+                    update_input_text_layout,
+                    update_input_rows_height,
+                    spawn_drag_ghosts,
+                    update_drag_ghosts,
                     update_mutations.run_if(|receiver: Res<EcsReceiver>| !receiver.is_empty()),
-                    update_interaction_classes,
+                    refresh_element_states,
                     handle_apc.run_if(|rpc_receiver: Res<EcsApcReceiver>| !rpc_receiver.is_empty()),
                 ),
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    dispatch_wheel_events.after(PickSet::Focus),
+                    count_pointer_clicks.after(PickSet::Last),
+                    (sync_tailwind_theme_asset, apply_tailwind_theme).chain(),
+                    update_viewport_width,
+                    refresh_color_scheme,
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    apply_stylesheets.before(update_transitions),
+                    update_transitions.before(UiSystem::Layout),
+                    update_animations
+                        .after(update_transitions)
+                        .before(UiSystem::Layout),
+                    update_scroll_view_layout
+                        .after(UiSystem::Layout)
+                        .before(TransformSystem::TransformPropagate),
+                    apply_ui_translations
+                        .after(update_scroll_view_layout)
+                        .before(TransformSystem::TransformPropagate),
+                ),
             );
+        app.add_systems(Last, end_drags);
+        add_dom_event_systems(app);
         let _handle = std::thread::spawn({
             let type_registry = app.world.resource::<AppTypeRegistry>().clone();
             move || {
@@ -205,37 +308,157 @@ fn update_mutations(vdom_receiver: Res<EcsReceiver>, mut commands: Deferred<Comm
     }
 }
 
-pub struct HandleInteractionClassesCommand(Vec<Entity>);
+pub struct UpdateElementStatesCommand(Vec<Entity>);
 
-impl Command for HandleInteractionClassesCommand {
+impl Command for UpdateElementStatesCommand {
     fn apply(self, world: &mut World) {
         let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let element_registry = world.resource::<ElementRegistry>().clone();
         world.resource_scope(|world, mut entities_extra_data: Mut<EntitiesExtraData>| {
             for entity in self.0.into_iter() {
-                handle_interaction_classes(&mut SetAttrValueContext {
+                if world.get_entity(entity).is_none()
+                    || !entities_extra_data.contains_key(&entity)
+                {
+                    continue;
+                }
+                update_element_states(&mut SetAttrValueContext {
                     entity_ref: &mut world.entity_mut(entity),
                     entities_extra_data: entities_extra_data.deref_mut(),
                     type_registry: type_registry.clone(),
+                    element_registry: element_registry.clone(),
                 });
             }
         });
     }
 }
 
-fn update_interaction_classes(
+/// Parses the class attr of the elements again, all of them and the templates when `None`
+pub struct ReapplyClassesCommand(pub Option<Vec<Entity>>);
+
+impl Command for ReapplyClassesCommand {
+    fn apply(self, world: &mut World) {
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let element_registry = world.resource::<ElementRegistry>().clone();
+        if self.0.is_none() {
+            world.resource_scope(|_world, mut template_world: Mut<TemplateWorld>| {
+                reapply_classes_in_world(
+                    &mut template_world,
+                    None,
+                    type_registry.clone(),
+                    element_registry.clone(),
+                );
+            });
+        }
+        reapply_classes_in_world(world, self.0, type_registry, element_registry);
+    }
+}
+
+fn reapply_classes_in_world(
+    world: &mut World,
+    entities: Option<Vec<Entity>>,
+    type_registry: AppTypeRegistry,
+    element_registry: ElementRegistry,
+) {
+    world.resource_scope(|world, mut entities_extra_data: Mut<EntitiesExtraData>| {
+        let entities = entities.unwrap_or_else(|| {
+            entities_extra_data
+                .iter()
+                .filter(|(_, n)| !n.classes.is_empty())
+                .map(|(entity, _)| *entity)
+                .collect()
+        });
+        for entity in entities.into_iter() {
+            if world.get_entity(entity).is_none() || !entities_extra_data.contains_key(&entity) {
+                continue;
+            }
+            reapply_classes(&mut SetAttrValueContext {
+                entity_ref: &mut world.entity_mut(entity),
+                entities_extra_data: entities_extra_data.deref_mut(),
+                type_registry: type_registry.clone(),
+                element_registry: element_registry.clone(),
+            });
+        }
+    });
+}
+
+fn refresh_element_states(
     mut commands: Commands,
-    entities: Query<Entity, (With<InteractionClass>, Changed<Interaction>)>,
+    hovered: Query<Entity, (With<InteractionClass>, Changed<Interaction>)>,
+    groups: Query<Entity, (With<UiGroup>, Changed<Interaction>)>,
+    changed_children: Query<&Children, Changed<Children>>,
+    children_query: Query<&Children>,
+    with_classes: Query<(), With<InteractionClass>>,
+    focus: Option<Res<Focus>>,
+    mut last_focus: Local<Option<Entity>>,
 ) {
+    let mut entities: Vec<Entity> = hovered.iter().collect();
+    for group in groups.iter() {
+        entities.extend(children_query.iter_descendants(group));
+    }
+    for children in changed_children.iter() {
+        entities.extend(children.iter().copied());
+    }
+    if let Some(focus) = focus.filter(|n| n.is_changed()) {
+        entities.extend(last_focus.take());
+        entities.extend(focus.0);
+        *last_focus = focus.0;
+    }
+    entities.retain(|entity| with_classes.contains(*entity));
     if entities.is_empty() {
         return;
     }
-    commands.add(HandleInteractionClassesCommand(entities.iter().collect()));
+    entities.sort();
+    entities.dedup();
+    commands.add(UpdateElementStatesCommand(entities));
+}
+
+fn update_viewport_width(
+    mut commands: Commands,
+    windows: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut viewport: ResMut<UiViewportWidth>,
+    theme: Res<TailwindTheme>,
+    with_classes: Query<Entity, With<InteractionClass>>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let width = window.width();
+    if viewport.0 == width {
+        return;
+    }
+    let previous = breakpoint_states(viewport.0, &theme);
+    viewport.0 = width;
+    if breakpoint_states(width, &theme) != previous {
+        commands.add(UpdateElementStatesCommand(with_classes.iter().collect()));
+    }
 }
 
-fn focus_input(
-    inputs: Query<Entity, (Without<ReadOnly>, Added<CosmicText>)>,
+fn refresh_color_scheme(
     mut commands: Commands,
+    color_scheme: Res<UiColorScheme>,
+    with_classes: Query<Entity, With<InteractionClass>>,
 ) {
+    if !color_scheme.is_changed() {
+        return;
+    }
+    let entities: Vec<Entity> = with_classes.iter().collect();
+    if !entities.is_empty() {
+        commands.add(UpdateElementStatesCommand(entities));
+    }
+}
+
+// the layout sets the translation every frame, the offset is added on top like the scroll offset
+fn apply_ui_translations(mut nodes: Query<(&UiTranslation, &Node, &mut Transform)>) {
+    for (translation, node, mut transform) in nodes.iter_mut() {
+        let offset = translation.resolve(node.size());
+        if offset != Vec2::ZERO {
+            transform.translation += offset.extend(0.0);
+        }
+    }
+}
+
+// readonly and disabled are checked on click, the attr can be cleared after the input is spawned
+fn focus_input(inputs: Query<Entity, Added<CosmicText>>, mut commands: Commands) {
     for entity in inputs.iter() {
         commands
             .entity(entity)
@@ -243,6 +466,23 @@ fn focus_input(
     }
 }
 
-fn on_click_input(listener_input: Res<ListenerInput<Pointer<Click>>>, mut focus: ResMut<Focus>) {
-    focus.0 = Some(listener_input.target);
+fn on_click_input(
+    listener_input: Res<ListenerInput<Pointer<Click>>>,
+    mut focus: ResMut<Focus>,
+    read_only: Query<(), With<ReadOnly>>,
+    disabled: Query<(), With<Disabled>>,
+    parents: Query<&Parent>,
+) {
+    let target = listener_input.target;
+    if read_only.contains(target) {
+        return;
+    }
+    let mut entity = Some(target);
+    while let Some(current) = entity {
+        if disabled.contains(current) {
+            return;
+        }
+        entity = parents.get(current).ok().map(|n| n.get());
+    }
+    focus.0 = Some(target);
 }
@@ -10,8 +10,8 @@ use bevy::prelude::{
 use dioxus::core::ElementId;
 
 use crate::{
-    ecs_fns, elements, ElementTypeBase, get_element_type, NodeTemplate, SetAttrValueContext,
-    TemplateWorld,
+    ecs_fns, elements, ElementRegistry, ElementTypeBase, get_element_type, NodeTemplate,
+    SetAttrValueContext, TemplateWorld,
 };
 use crate::dom_template::{DomTemplate, DomTemplateAttribute, DomTemplateNode};
 use crate::ecs_fns::{insert_after, insert_before, WorldExtension};
@@ -27,6 +27,7 @@ pub fn create_template_node(
     entities_extra_data: &mut EntitiesExtraData,
     template_node: DomTemplateNode,
     type_registry: AppTypeRegistry,
+    element_registry: ElementRegistry,
 ) -> Entity {
     match template_node {
         DomTemplateNode::Element {
@@ -44,6 +45,7 @@ pub fn create_template_node(
                         entities_extra_data,
                         n,
                         type_registry.clone(),
+                        element_registry.clone(),
                     ));
                 }
                 entities
@@ -59,12 +61,12 @@ pub fn create_template_node(
                     }
                 })
                 .collect::<Vec<_>>();
-            let schema_type = get_element_type(tag);
+            let schema_type = get_element_type(&element_registry, tag);
             let mut entity_ref = schema_type.spawn(template_world);
             let entity = entity_ref.id();
             let mut entity_extra_data = EntityExtraData::new(tag);
             for (name, _) in static_attrs.iter() {
-                let Some(attr) = schema_type.attr(&name) else {
+                let Some(attr) = schema_type.attr(&element_registry, name) else {
                     warn!("no found attr {:?}", name);
                     continue;
                 };
@@ -76,9 +78,10 @@ pub fn create_template_node(
                 entity_ref: &mut entity_ref,
                 entities_extra_data,
                 type_registry,
+                element_registry: element_registry.clone(),
             };
             for (name, value) in static_attrs.into_iter() {
-                let Some(attr) = schema_type.attr(&name) else {
+                let Some(attr) = schema_type.attr(&element_registry, &name) else {
                     warn!("no found attr {:?}", name);
                     continue;
                 };
@@ -139,6 +142,7 @@ pub struct CreateTemplates {
 impl Command for CreateTemplates {
     fn apply(self, world: &mut World) {
         let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let element_registry = world.resource::<ElementRegistry>().clone();
         let mut template_world = world.resource_mut::<TemplateWorld>();
         template_world.resource_scope(
             |template_world, mut entities_extra_data: Mut<EntitiesExtraData>| {
@@ -152,6 +156,7 @@ impl Command for CreateTemplates {
                                     entities_extra_data.as_mut(),
                                     n,
                                     type_registry.clone(),
+                                    element_registry.clone(),
                                 ));
                             }
 
@@ -520,6 +525,28 @@ impl From<DomAttributeValue> for Option<i64> {
     }
 }
 
+impl From<DomAttributeValue> for Option<u32> {
+    fn from(value: DomAttributeValue) -> Self {
+        match value {
+            DomAttributeValue::Text(value) => value.parse().ok(),
+            DomAttributeValue::Int(value) => u32::try_from(value).ok(),
+            DomAttributeValue::Float(value) => Some(value as u32),
+            _ => None,
+        }
+    }
+}
+
+impl From<DomAttributeValue> for Option<u64> {
+    fn from(value: DomAttributeValue) -> Self {
+        match value {
+            DomAttributeValue::Text(value) => value.parse().ok(),
+            DomAttributeValue::Int(value) => u64::try_from(value).ok(),
+            DomAttributeValue::Float(value) => Some(value as u64),
+            _ => None,
+        }
+    }
+}
+
 impl From<DomAttributeValue> for Option<f32> {
     fn from(value: DomAttributeValue) -> Self {
         match value {
@@ -551,6 +578,7 @@ impl Command for SetAttribute {
     fn apply(self, world: &mut World) {
         let vdom_data = world.resource::<VDomData>();
         let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let element_registry = world.resource::<ElementRegistry>().clone();
         let entity = vdom_data.element_id_to_entity[&self.id];
 
         // todo: handle set attr value error
@@ -560,22 +588,28 @@ impl Command for SetAttribute {
                 error!("No Found EntityExtraData by {:#?}", entity);
                 return;
             };
-            let schema_type = get_element_type(entity_extra_data.schema_name);
-            match schema_type.attr(self.name) {
+            let schema_type = get_element_type(&element_registry, entity_extra_data.schema_name);
+            match schema_type.attr(&element_registry, self.name) {
                 Some(attr) => {
-                    entity_extra_data
-                        .set_attr(attr.index(), !matches!(self.value, DomAttributeValue::None));
+                    let value_is_some = !matches!(self.value, DomAttributeValue::None);
+                    entity_extra_data.set_attr(attr.index(), value_is_some);
 
                     let mut entity_ref = world.entity_mut(entity);
-
-                    attr.set_by_attr_value(
-                        &mut SetAttrValueContext {
-                            entity_ref: &mut entity_ref,
-                            entities_extra_data: entities_extra_data.deref_mut(),
-                            type_registry,
-                        },
-                        self.value,
-                    );
+                    let mut context = SetAttrValueContext {
+                        entity_ref: &mut entity_ref,
+                        entities_extra_data: entities_extra_data.deref_mut(),
+                        type_registry,
+                        element_registry,
+                    };
+                    attr.set_by_attr_value(&mut context, self.value);
+                    // a cleared attr shows the class or stylesheet value again
+                    if !value_is_some {
+                        if let Some(value) =
+                            context.entity_extra_data().fallback_value(attr.index())
+                        {
+                            attr.set_dyn_value(&mut context, value);
+                        }
+                    }
                 }
                 None => {
                     let Some(attr) = schema_type.composite_attr(self.name) else {
@@ -590,6 +624,7 @@ impl Command for SetAttribute {
                             entity_ref: &mut entity_ref,
                             entities_extra_data: entities_extra_data.deref_mut(),
                             type_registry,
+                            element_registry,
                         },
                         self.value,
                     ) else {
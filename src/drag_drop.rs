@@ -0,0 +1,239 @@
+use std::any::Any;
+use std::cell::RefCell;
+
+use bevy::ecs::system::Command;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_mod_picking::prelude::{Drag, DragEnd, DragStart, Pickable, Pointer, PointerId};
+
+use crate::apc;
+use crate::element_core::UiEvent;
+use crate::ui_events::{ui_node_rect, PointerEventData};
+use crate::{DomApcSender, UiTranslation};
+
+thread_local! {
+    static DATA_TRANSFER: RefCell<HashMap<(PointerId, Entity), Box<dyn Any>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Payloads of the running drags by pointer and dragged element, only accessible from dioxus
+/// handlers (the vdom thread). A payload is cleared once its drag ends.
+pub struct DataTransfer;
+
+impl DataTransfer {
+    pub fn set<T: 'static>(pointer_id: PointerId, dragged: Entity, value: T) {
+        DATA_TRANSFER.with(|n| n.borrow_mut().insert((pointer_id, dragged), Box::new(value)));
+    }
+
+    pub fn get<T: Clone + 'static>(pointer_id: PointerId, dragged: Entity) -> Option<T> {
+        DATA_TRANSFER.with(|n| {
+            n.borrow()
+                .get(&(pointer_id, dragged))?
+                .downcast_ref::<T>()
+                .cloned()
+        })
+    }
+
+    pub fn has<T: 'static>(pointer_id: PointerId, dragged: Entity) -> bool {
+        DATA_TRANSFER.with(|n| {
+            n.borrow()
+                .get(&(pointer_id, dragged))
+                .is_some_and(|n| n.is::<T>())
+        })
+    }
+
+    pub fn take<T: 'static>(pointer_id: PointerId, dragged: Entity) -> Option<T> {
+        DATA_TRANSFER.with(|n| {
+            let mut data = n.borrow_mut();
+            if !data.get(&(pointer_id, dragged))?.is::<T>() {
+                return None;
+            }
+            data.remove(&(pointer_id, dragged))?.downcast().ok().map(|n| *n)
+        })
+    }
+
+    pub fn clear(pointer_id: PointerId, dragged: Entity) {
+        DATA_TRANSFER.with(|n| n.borrow_mut().remove(&(pointer_id, dragged)));
+    }
+}
+
+impl<E> UiEvent<PointerEventData<E>> {
+    /// Sets the payload of the drag this event belongs to, ignored for other pointer events
+    pub fn set_drag_data<T: 'static>(&self, value: T) {
+        let data = self.data();
+        if let Some(dragged) = data.dragged {
+            DataTransfer::set(data.pointer_id, dragged, value);
+        }
+    }
+
+    pub fn drag_data<T: Clone + 'static>(&self) -> Option<T> {
+        let data = self.data();
+        DataTransfer::get(data.pointer_id, data.dragged?)
+    }
+}
+
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component, Default)]
+pub struct Draggable;
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DragGhost {
+    pub pointer_id: PointerId,
+    /// target of the drag events, the draggable element or one of its descendants
+    pub dragged: Entity,
+    /// draggable element the ghost is cloned from
+    pub source: Entity,
+    /// pointer position relative to the top left of the dragged element
+    pub grab_offset: Vec2,
+}
+
+pub fn find_draggable(world: &World, entity: Entity) -> Option<Entity> {
+    let mut entity = Some(entity);
+    while let Some(current) = entity {
+        if world.get::<Draggable>(current).is_some() {
+            return Some(current);
+        }
+        entity = world.get::<Parent>(current).map(|n| n.get());
+    }
+    None
+}
+
+fn clone_ghost_node(world: &mut World, source: Entity) -> Entity {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let components = {
+        let source_ref = world.entity(source);
+        source_ref
+            .archetype()
+            .components()
+            .filter_map(|component_id| {
+                let type_id = world.components().get_info(component_id)?.type_id()?;
+                if type_id == std::any::TypeId::of::<Parent>()
+                    || type_id == std::any::TypeId::of::<Children>()
+                    || type_id == std::any::TypeId::of::<Draggable>()
+                    || type_id == std::any::TypeId::of::<Interaction>()
+                {
+                    return None;
+                }
+                let reflect_component = type_registry.get_type_data::<ReflectComponent>(type_id)?;
+                let value = reflect_component.reflect(source_ref)?.clone_value();
+                Some((reflect_component.clone(), value))
+            })
+            .collect::<Vec<_>>()
+    };
+    let children = world
+        .get::<Children>(source)
+        .map(|n| n.to_vec())
+        .unwrap_or_default();
+
+    let mut ghost = world.spawn_empty();
+    for (reflect_component, value) in components {
+        reflect_component.insert(&mut ghost, &*value);
+    }
+    // the ghost must not hide drop targets from the pointer
+    ghost.insert(Pickable::IGNORE);
+    let ghost = ghost.id();
+    drop(type_registry);
+    for child in children {
+        let child_ghost = clone_ghost_node(world, child);
+        world.entity_mut(ghost).add_child(child_ghost);
+    }
+    ghost
+}
+
+pub struct SpawnDragGhost {
+    pub pointer_id: PointerId,
+    pub dragged: Entity,
+    pub source: Entity,
+    pub pointer_position: Vec2,
+}
+
+impl Command for SpawnDragGhost {
+    fn apply(self, world: &mut World) {
+        let Some(rect) = ui_node_rect(world, self.source) else {
+            return;
+        };
+        let ghost = clone_ghost_node(world, self.source);
+        let grab_offset = self.pointer_position - rect.min;
+        let mut ghost_ref = world.entity_mut(ghost);
+        // the rect already includes the translation
+        ghost_ref.remove::<UiTranslation>();
+        ghost_ref.insert((
+            DragGhost {
+                pointer_id: self.pointer_id,
+                dragged: self.dragged,
+                source: self.source,
+                grab_offset,
+            },
+            ZIndex::Global(i32::MAX),
+        ));
+        if let Some(mut style) = ghost_ref.get_mut::<Style>() {
+            style.position_type = PositionType::Absolute;
+            style.left = Val::Px(rect.min.x);
+            style.top = Val::Px(rect.min.y);
+            style.width = Val::Px(rect.width());
+            style.height = Val::Px(rect.height());
+            style.margin = UiRect::all(Val::Px(0.));
+        }
+    }
+}
+
+pub fn spawn_drag_ghosts(
+    world: &World,
+    mut drag_starts: EventReader<Pointer<DragStart>>,
+    mut commands: Commands,
+) {
+    for drag_start in drag_starts.iter() {
+        let Some(source) = find_draggable(world, drag_start.target) else {
+            continue;
+        };
+        commands.add(SpawnDragGhost {
+            pointer_id: drag_start.pointer_id,
+            dragged: drag_start.target,
+            source,
+            pointer_position: drag_start.pointer_location.position,
+        });
+    }
+}
+
+pub fn update_drag_ghosts(
+    mut drags: EventReader<Pointer<Drag>>,
+    mut ghosts: Query<(&DragGhost, &mut Style)>,
+) {
+    for drag in drags.iter() {
+        let position = drag.pointer_location.position;
+        for (ghost, mut style) in ghosts.iter_mut() {
+            if ghost.pointer_id != drag.pointer_id || ghost.dragged != drag.target {
+                continue;
+            }
+            style.left = Val::Px(position.x - ghost.grab_offset.x);
+            style.top = Val::Px(position.y - ghost.grab_offset.y);
+        }
+    }
+}
+
+// Runs in `Last`, after the drop and dragend handlers were sent to the vdom thread.
+pub fn end_drags(
+    mut commands: Commands,
+    mut drag_ends: EventReader<Pointer<DragEnd>>,
+    ghosts: Query<(Entity, &DragGhost)>,
+    apc_sender: Res<DomApcSender>,
+) {
+    let ended = drag_ends
+        .iter()
+        .map(|drag_end| (drag_end.pointer_id, drag_end.target))
+        .collect::<Vec<_>>();
+    if ended.is_empty() {
+        return;
+    }
+    for (entity, ghost) in ghosts.iter() {
+        if ended.contains(&(ghost.pointer_id, ghost.dragged)) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+    apc::call(&apc_sender.0, move |_| {
+        for (pointer_id, dragged) in ended {
+            DataTransfer::clear(pointer_id, dragged);
+        }
+    });
+}
@@ -2,6 +2,7 @@ use std::any::{Any, TypeId};
 use std::mem::ManuallyDrop;
 use std::ops::DerefMut;
 use std::ptr::NonNull;
+use std::sync::Arc;
 
 use bevy::ecs::component::ComponentInfo;
 use bevy::ecs::world::EntityMut;
@@ -13,7 +14,11 @@ use bevy::ptr::OwningPtr;
 use bevy::reflect::{ReflectFromPtr, TypeRegistryInternal as TypeRegistry};
 
 use crate::entity_extra_data::EntitiesExtraData;
-use crate::get_element_type;
+use crate::{get_element_type, ElementRegistry};
+use crate::tailwind::{
+    report_unknown_classes, ParsedClasses, TailwindClassCache, TailwindDiagnostics,
+    TailwindTheme,
+};
 use crate::prelude::{
     default, Display, error, Name, NodeBundle, ReflectComponent,
 };
@@ -70,7 +75,10 @@ fn clone_entity<'a>(
         ));
     };
     let type_registry = world.resource::<AppTypeRegistry>().clone();
-    let schema_type = get_element_type(entity_extra_data.schema_name);
+    let schema_type = get_element_type(
+        world.resource::<ElementRegistry>(),
+        entity_extra_data.schema_name,
+    );
     let ignore_type_ids = vec![TypeId::of::<Parent>(), TypeId::of::<Children>()];
 
     let mut components = vec![];
@@ -158,6 +166,22 @@ fn clone_entity<'a>(
             components.into_iter().map(dyn_to_owning_ptr),
         )
     };
+    // classes of templates are parsed in the template world, report them for each instance
+    let world = loaded_entity.world();
+    if let Some(theme) = world.get_resource::<TailwindTheme>().filter(|_| {
+        !entity_extra_data.classes.is_empty()
+            && world
+                .get_resource::<TailwindDiagnostics>()
+                .is_some_and(|n| n.is_enabled())
+    }) {
+        let parsed = match world.get_resource::<TailwindClassCache>() {
+            Some(cache) => cache.get_or_parse(theme, &entity_extra_data.classes),
+            None => Arc::new(ParsedClasses::parse(theme, &entity_extra_data.classes)),
+        };
+        let (entity, tag) = (loaded_entity.id(), entity_extra_data.schema_name);
+        loaded_entity
+            .world_scope(|world| report_unknown_classes(world, entity, tag, &parsed.unknown));
+    }
     entities_extra_data.insert(loaded_entity.id(), entity_extra_data);
     loaded_entity
 }
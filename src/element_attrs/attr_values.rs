@@ -1,6 +1,6 @@
 use bevy::asset::Asset;
 use bevy::prelude::*;
-use bevy::reflect::TypePath;
+use bevy::reflect::{ReflectRef, TypePath};
 use bevy::text::BreakLineOn;
 use dioxus::prelude::{AnyValue, IntoAttributeValue};
 use serde::{Deserialize, Serialize};
@@ -9,6 +9,9 @@ use crate::{
     ElementTypeUnTyped, from_str, impl_default_attr_value, MyFromStr, smallbox,
     SmallBox,
 };
+use crate::animation::{
+    AnimationHandle, AnimationSpec, Easing, TransitionProperties, TransitionShorthand,
+};
 use crate::dom_commands::DomAttributeValue;
 use crate::element_core::AttrValue;
 use crate::smallbox::S1;
@@ -231,6 +234,97 @@ impl UiOptionalRect {
     }
 }
 
+/// Reflected components attached to an element by the `components` attr
+#[derive(Default, Reflect)]
+#[reflect_value]
+pub struct UiComponents(pub Vec<Box<dyn Reflect>>);
+
+impl UiComponents {
+    pub fn with(mut self, component: impl Reflect) -> Self {
+        self.0.push(Box::new(component));
+        self
+    }
+}
+
+impl Clone for UiComponents {
+    fn clone(&self) -> Self {
+        UiComponents(self.0.iter().map(|n| n.clone_value()).collect())
+    }
+}
+
+impl PartialEq for UiComponents {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(other.0.iter())
+                .all(|(a, b)| reflect_fields_eq(a.as_reflect(), b.as_reflect()))
+    }
+}
+
+/// Compares types and reflected fields, for values without `#[reflect(PartialEq)]`
+pub fn reflect_fields_eq(a: &dyn Reflect, b: &dyn Reflect) -> bool {
+    if a.type_name() != b.type_name() {
+        return false;
+    }
+    if let Some(eq) = a.reflect_partial_eq(b) {
+        return eq;
+    }
+    let fields_eq = |a: Option<&dyn Reflect>, b: Option<&dyn Reflect>| match (a, b) {
+        (Some(a), Some(b)) => reflect_fields_eq(a, b),
+        _ => false,
+    };
+    match (a.reflect_ref(), b.reflect_ref()) {
+        (ReflectRef::Struct(a), ReflectRef::Struct(b)) => {
+            a.field_len() == b.field_len()
+                && (0..a.field_len()).all(|i| {
+                    a.name_at(i) == b.name_at(i) && fields_eq(a.field_at(i), b.field_at(i))
+                })
+        }
+        (ReflectRef::TupleStruct(a), ReflectRef::TupleStruct(b)) => {
+            a.field_len() == b.field_len()
+                && (0..a.field_len()).all(|i| fields_eq(a.field(i), b.field(i)))
+        }
+        (ReflectRef::Tuple(a), ReflectRef::Tuple(b)) => {
+            a.field_len() == b.field_len()
+                && (0..a.field_len()).all(|i| fields_eq(a.field(i), b.field(i)))
+        }
+        (ReflectRef::List(a), ReflectRef::List(b)) => {
+            a.len() == b.len() && (0..a.len()).all(|i| fields_eq(a.get(i), b.get(i)))
+        }
+        (ReflectRef::Array(a), ReflectRef::Array(b)) => {
+            a.len() == b.len() && (0..a.len()).all(|i| fields_eq(a.get(i), b.get(i)))
+        }
+        (ReflectRef::Map(a), ReflectRef::Map(b)) => {
+            a.len() == b.len() && a.iter().all(|(key, value)| fields_eq(Some(value), b.get(key)))
+        }
+        (ReflectRef::Enum(a), ReflectRef::Enum(b)) => {
+            a.variant_name() == b.variant_name()
+                && a.field_len() == b.field_len()
+                && (0..a.field_len()).all(|i| fields_eq(a.field_at(i), b.field_at(i)))
+        }
+        _ => false,
+    }
+}
+
+impl<'a> IntoAttributeValue<'a> for UiComponents {
+    fn into_value(
+        self,
+        bump: &'a dioxus::core::exports::bumpalo::Bump,
+    ) -> dioxus::core::AttributeValue<'a> {
+        Attr(self).into_value(bump)
+    }
+}
+
+/// `ui_components![Marker, Health(3)]`
+#[macro_export]
+macro_rules! ui_components {
+    ($($component:expr),* $(,)?) => {
+        $crate::UiComponents::default()$(.with($component))*
+    };
+}
+
 #[derive(Debug, Default, Reflect, Clone)]
 pub struct TextSections(pub Vec<TextSection>);
 
@@ -719,6 +813,11 @@ impl_from_attr_value!(Visibility);
 impl_from_attr_value!(BreakLineOn);
 impl_from_attr_value_only_dyn!(Transform);
 impl_from_attr_value_only_dyn!(OptionalTransform);
+impl_from_attr_value_only_dyn!(UiComponents);
+impl_from_attr_value!(TransitionShorthand);
+impl_from_attr_value!(Easing);
+impl_from_attr_value!(AnimationSpec);
+impl_from_attr_value_only_dyn!(AnimationHandle);
 
 impl_default_attr_value!(UiTexture, UiTexture::Color(Color::rgba_u8(0, 0, 0, 0)));
 impl_default_attr_value!(Color, Color::rgba_u8(0, 0, 0, 0));
@@ -741,8 +840,345 @@ impl_default_attr_value!(FlexDirection);
 impl_default_attr_value!(FlexWrap);
 impl_default_attr_value!(Visibility);
 impl_default_attr_value!(TextSections);
+impl_default_attr_value!(UiComponents);
+impl_default_attr_value!(
+    TransitionShorthand,
+    TransitionShorthand {
+        properties: Some(TransitionProperties::NONE),
+        ..Default::default()
+    }
+);
+impl_default_attr_value!(Easing);
+impl_default_attr_value!(AnimationSpec);
+impl_default_attr_value!(AnimationHandle);
 impl_default_attr_value!(ZIndex);
 impl_default_attr_value!(Transform);
 impl_default_attr_value!(Quat);
 impl_default_attr_value!(Vec3);
 impl_default_attr_value!(OverflowAxis);
+
+/// Splits on whitespace outside of parentheses, `"repeat(2, 1fr) 100px"` → `["repeat(2, 1fr)", "100px"]`
+fn split_grid_tracks(s: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = None;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    parts.push(&s[start..i]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(start) = start {
+        parts.push(&s[start..]);
+    }
+    parts
+}
+
+fn parse_grid_length(s: &str) -> Option<(f32, &'static str)> {
+    if let Some(n) = s.strip_suffix("px") {
+        Some((n.parse().ok()?, "px"))
+    } else if let Some(n) = s.strip_suffix('%') {
+        Some((n.parse().ok()?, "%"))
+    } else if let Some(n) = s.strip_suffix("fr") {
+        Some((n.parse().ok()?, "fr"))
+    } else {
+        Some((s.parse().ok()?, "px"))
+    }
+}
+
+impl MyFromStr for MinTrackSizingFunction {
+    fn from_str(s: &str) -> Option<Self> {
+        Some(match s {
+            "auto" => MinTrackSizingFunction::Auto,
+            "min-content" => MinTrackSizingFunction::MinContent,
+            "max-content" => MinTrackSizingFunction::MaxContent,
+            s => match parse_grid_length(s)? {
+                (n, "px") => MinTrackSizingFunction::Px(n),
+                (n, "%") => MinTrackSizingFunction::Percent(n),
+                _ => return None,
+            },
+        })
+    }
+}
+
+impl MyFromStr for MaxTrackSizingFunction {
+    fn from_str(s: &str) -> Option<Self> {
+        Some(match s {
+            "auto" => MaxTrackSizingFunction::Auto,
+            "min-content" => MaxTrackSizingFunction::MinContent,
+            "max-content" => MaxTrackSizingFunction::MaxContent,
+            s => match parse_grid_length(s)? {
+                (n, "px") => MaxTrackSizingFunction::Px(n),
+                (n, "%") => MaxTrackSizingFunction::Percent(n),
+                (n, _) => MaxTrackSizingFunction::Fraction(n),
+            },
+        })
+    }
+}
+
+impl MyFromStr for GridTrack {
+    fn from_str(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if let Some(args) = s.strip_prefix("minmax(").and_then(|n| n.strip_suffix(')')) {
+            let (min, max) = args.split_once(',')?;
+            return Some(GridTrack::minmax(
+                from_str(min.trim())?,
+                from_str(max.trim())?,
+            ));
+        }
+        if let Some(arg) = s.strip_prefix("fit-content(").and_then(|n| n.strip_suffix(')')) {
+            return match parse_grid_length(arg.trim())? {
+                (n, "px") => Some(GridTrack::fit_content_px(n)),
+                (n, "%") => Some(GridTrack::fit_content_percent(n)),
+                _ => None,
+            };
+        }
+        Some(match s {
+            "auto" => GridTrack::auto(),
+            "min-content" => GridTrack::min_content(),
+            "max-content" => GridTrack::max_content(),
+            s => match parse_grid_length(s)? {
+                (n, "px") => GridTrack::px(n),
+                (n, "%") => GridTrack::percent(n),
+                (n, _) => GridTrack::fr(n),
+            },
+        })
+    }
+}
+
+impl MyFromStr for Vec<GridTrack> {
+    fn from_str(s: &str) -> Option<Self> {
+        split_grid_tracks(s).into_iter().map(from_str).collect()
+    }
+}
+
+impl MyFromStr for RepeatedGridTrack {
+    fn from_str(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let Some(args) = s.strip_prefix("repeat(").and_then(|n| n.strip_suffix(')')) else {
+            return from_str::<GridTrack>(s).map(Into::into);
+        };
+        let (repetition, tracks) = args.split_once(',')?;
+        let repetition = match repetition.trim() {
+            "auto-fill" => GridTrackRepetition::AutoFill,
+            "auto-fit" => GridTrackRepetition::AutoFit,
+            n => GridTrackRepetition::Count(n.parse().ok()?),
+        };
+        Some(RepeatedGridTrack::repeat_many(
+            repetition,
+            from_str::<Vec<GridTrack>>(tracks).filter(|n| !n.is_empty())?,
+        ))
+    }
+}
+
+impl MyFromStr for Vec<RepeatedGridTrack> {
+    fn from_str(s: &str) -> Option<Self> {
+        split_grid_tracks(s).into_iter().map(from_str).collect()
+    }
+}
+
+enum GridLine {
+    Auto,
+    Line(i16),
+    Span(u16),
+}
+
+impl MyFromStr for GridLine {
+    fn from_str(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if s == "auto" {
+            Some(GridLine::Auto)
+        } else if let Some(span) = s.strip_prefix("span") {
+            // bevy panics on a zero span or line
+            Some(GridLine::Span(span.trim().parse().ok().filter(|n| *n != 0)?))
+        } else {
+            Some(GridLine::Line(s.parse().ok().filter(|n| *n != 0)?))
+        }
+    }
+}
+
+/// `"2"`, `"span 2"`, `"1 / 3"`, `"1 / span 2"`, `"span 2 / -1"`
+impl MyFromStr for GridPlacement {
+    fn from_str(s: &str) -> Option<Self> {
+        let (start, end) = match s.split_once('/') {
+            Some((start, end)) => (from_str(start)?, from_str(end)?),
+            None => (from_str(s)?, GridLine::Auto),
+        };
+        Some(match (start, end) {
+            (GridLine::Auto, GridLine::Auto) => GridPlacement::auto(),
+            (GridLine::Auto, GridLine::Line(end)) => GridPlacement::end(end),
+            // like css, the end span is ignored when the start is a span too
+            (GridLine::Span(span), GridLine::Auto | GridLine::Span(_))
+            | (GridLine::Auto, GridLine::Span(span)) => GridPlacement::span(span),
+            (GridLine::Line(start), GridLine::Auto) => GridPlacement::start(start),
+            (GridLine::Line(start), GridLine::Line(end)) => GridPlacement::start_end(start, end),
+            (GridLine::Line(start), GridLine::Span(span)) => GridPlacement::start_span(start, span),
+            (GridLine::Span(span), GridLine::Line(end)) => GridPlacement::end_span(end, span),
+        })
+    }
+}
+
+/// Line of `grid_*_start` and `grid_*_end`, 0 or out of range clears it
+pub fn grid_line(value: i64) -> Option<i16> {
+    i16::try_from(value).ok().filter(|n| *n != 0)
+}
+
+/// Placement with its start line replaced, the end line and span are kept
+pub fn with_grid_start(placement: GridPlacement, start: Option<i16>) -> GridPlacement {
+    grid_placement(start, placement.get_end(), placement.get_span())
+}
+
+/// Placement with its end line replaced, the start line and span are kept
+pub fn with_grid_end(placement: GridPlacement, end: Option<i16>) -> GridPlacement {
+    grid_placement(placement.get_start(), end, placement.get_span())
+}
+
+/// The span only applies while one of the lines is missing
+fn grid_placement(start: Option<i16>, end: Option<i16>, span: Option<u16>) -> GridPlacement {
+    let span = span.unwrap_or(1);
+    match (start, end) {
+        (Some(start), Some(end)) => GridPlacement::start_end(start, end),
+        (Some(start), None) => GridPlacement::start_span(start, span),
+        (None, Some(end)) => GridPlacement::end_span(end, span),
+        (None, None) => GridPlacement::span(span),
+    }
+}
+
+impl MyFromStr for GridAutoFlow {
+    fn from_str(s: &str) -> Option<Self> {
+        match s.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["row"] => Some(GridAutoFlow::Row),
+            ["column"] => Some(GridAutoFlow::Column),
+            ["row", "dense"] | ["dense"] => Some(GridAutoFlow::RowDense),
+            ["column", "dense"] => Some(GridAutoFlow::ColumnDense),
+            _ => None,
+        }
+    }
+}
+
+impl_from_attr_value!(Vec<RepeatedGridTrack>);
+impl_from_attr_value!(Vec<GridTrack>);
+impl_from_attr_value!(GridPlacement);
+impl_from_attr_value!(GridAutoFlow);
+
+impl_default_attr_value!(Vec<RepeatedGridTrack>);
+impl_default_attr_value!(Vec<GridTrack>);
+impl_default_attr_value!(GridPlacement);
+impl_default_attr_value!(GridAutoFlow);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_tracks() {
+        let expected: Vec<GridTrack> = vec![
+            GridTrack::px(100.),
+            GridTrack::percent(50.),
+            GridTrack::fr(1.),
+            GridTrack::auto(),
+            GridTrack::px(20.),
+        ];
+        assert_eq!(from_str::<Vec<GridTrack>>("100px 50% 1fr auto 20"), Some(expected));
+        let expected: GridTrack = GridTrack::minmax(
+            MinTrackSizingFunction::Px(100.),
+            MaxTrackSizingFunction::Fraction(1.),
+        );
+        assert_eq!(from_str::<GridTrack>("minmax(100px, 1fr)"), Some(expected));
+        let expected: GridTrack = GridTrack::fit_content_percent(40.);
+        assert_eq!(from_str::<GridTrack>("fit-content(40%)"), Some(expected));
+    }
+
+    #[test]
+    fn invalid_grid_tracks() {
+        assert_eq!(from_str::<GridTrack>("10em"), None);
+        assert_eq!(from_str::<GridTrack>("minmax(100px)"), None);
+        // a fraction can't be the minimum
+        assert_eq!(from_str::<GridTrack>("minmax(1fr, 100px)"), None);
+        assert_eq!(from_str::<GridTrack>("fit-content(1fr)"), None);
+        assert_eq!(from_str::<Vec<GridTrack>>("1fr foo"), None);
+    }
+
+    #[test]
+    fn grid_template_repeat() {
+        let expected: Vec<RepeatedGridTrack> = vec![
+            RepeatedGridTrack::repeat_many(
+                GridTrackRepetition::Count(3),
+                vec![GridTrack::fr(1.), GridTrack::px(20.)],
+            ),
+            GridTrack::px(100.),
+        ];
+        assert_eq!(
+            from_str::<Vec<RepeatedGridTrack>>("repeat(3, 1fr 20px) 100px"),
+            Some(expected)
+        );
+        let expected: Vec<RepeatedGridTrack> = vec![RepeatedGridTrack::repeat_many(
+            GridTrackRepetition::AutoFill,
+            vec![GridTrack::minmax(
+                MinTrackSizingFunction::Px(100.),
+                MaxTrackSizingFunction::Fraction(1.),
+            )],
+        )];
+        assert_eq!(
+            from_str::<Vec<RepeatedGridTrack>>("repeat(auto-fill, minmax(100px, 1fr))"),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn invalid_grid_template() {
+        assert_eq!(from_str::<Vec<RepeatedGridTrack>>("repeat(0.5, 1fr)"), None);
+        assert_eq!(from_str::<Vec<RepeatedGridTrack>>("repeat(2 1fr)"), None);
+        assert_eq!(from_str::<Vec<RepeatedGridTrack>>("repeat(2, )"), None);
+        assert_eq!(from_str::<Vec<RepeatedGridTrack>>("repeat(2, 1fr) 1em"), None);
+    }
+
+    #[test]
+    fn grid_placement() {
+        assert_eq!(from_str::<GridPlacement>("auto"), Some(GridPlacement::auto()));
+        assert_eq!(from_str::<GridPlacement>("2"), Some(GridPlacement::start(2)));
+        assert_eq!(from_str::<GridPlacement>("span 2"), Some(GridPlacement::span(2)));
+        assert_eq!(from_str::<GridPlacement>("1 / 3"), Some(GridPlacement::start_end(1, 3)));
+        assert_eq!(
+            from_str::<GridPlacement>("1 / span 2"),
+            Some(GridPlacement::start_span(1, 2))
+        );
+        assert_eq!(
+            from_str::<GridPlacement>("span 2 / -1"),
+            Some(GridPlacement::end_span(-1, 2))
+        );
+        assert_eq!(from_str::<GridPlacement>("span 2 / span 3"), Some(GridPlacement::span(2)));
+        assert_eq!(from_str::<GridPlacement>("0"), None);
+        assert_eq!(from_str::<GridPlacement>("span 0"), None);
+        assert_eq!(from_str::<GridPlacement>("1 / x"), None);
+    }
+
+    #[test]
+    fn grid_auto_flow() {
+        assert_eq!(from_str::<GridAutoFlow>("column dense"), Some(GridAutoFlow::ColumnDense));
+        assert_eq!(from_str::<GridAutoFlow>("dense"), Some(GridAutoFlow::RowDense));
+        assert_eq!(from_str::<GridAutoFlow>("dense row"), None);
+    }
+
+    #[test]
+    fn grid_start_end_merge() {
+        let placement = with_grid_start(GridPlacement::auto(), grid_line(2));
+        assert_eq!(placement, GridPlacement::start(2));
+        let placement = with_grid_end(placement, grid_line(4));
+        assert_eq!(placement, GridPlacement::start_end(2, 4));
+        assert_eq!(with_grid_start(placement, grid_line(0)), GridPlacement::end(4));
+        assert_eq!(
+            with_grid_end(GridPlacement::span(3), grid_line(-1)),
+            GridPlacement::end_span(-1, 3)
+        );
+        assert_eq!(grid_line(i64::MAX), None);
+    }
+}
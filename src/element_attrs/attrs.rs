@@ -1,18 +1,29 @@
 #![allow(non_camel_case_types)]
 
+use bevy::ecs::world::EntityMut;
 use bevy::prelude::*;
 use bevy::text::BreakLineOn;
 use bevy::ui;
 use bevy::ui::widget::UiImageSize;
+use bevy_cosmic_edit::Focus;
+use bevy_dioxus_macro::ElementAttr;
+use bevy_mod_picking::prelude::Pickable;
 
 use crate::{
     set_text_value, SetAttrValueContext,
-    TextStyledElementType, UiTexture,
+    TextStyledElementType, UiComponents, UiTexture,
+};
+use crate::animation::{
+    restore_animation_base, update_transition, AnimationHandle, AnimationSpec, Easing,
+    TransitionShorthand, UiAnimation, UiTransition,
 };
 use crate::dom_commands::DomAttributeValue;
+use crate::drag_drop::Draggable;
 use crate::ecs_fns::StyleEntityExt;
+use crate::element_attrs::{grid_line, with_grid_end, with_grid_start};
 use crate::element_core::ElementAttr;
-use crate::tailwind::handle_classes;
+use crate::entity_extra_data::{Checked, Disabled, PickableBeforeDisabled};
+use crate::tailwind::{handle_classes, update_element_states};
 
 pub struct class;
 
@@ -51,18 +62,10 @@ impl ElementAttr for name {
     }
 }
 
+#[derive(ElementAttr)]
+#[element_attr(component = ZIndex)]
 pub struct z_index;
 
-impl ElementAttr for z_index {
-    type Value = ZIndex;
-
-    const TAG_NAME: &'static str = stringify!(z_index);
-
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.insert(value.into());
-    }
-}
-
 pub struct background;
 
 impl ElementAttr for background {
@@ -122,696 +125,649 @@ impl ElementAttr for background {
     }
 }
 
+#[derive(ElementAttr)]
+#[element_attr(style.border.left = Val)]
 pub struct border_left;
 
-impl ElementAttr for border_left {
-    type Value = Val;
-
-    const TAG_NAME: &'static str = stringify!(border_left);
-
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.border.left = value;
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.border.right = Val)]
 pub struct border_right;
 
-impl ElementAttr for border_right {
-    type Value = Val;
-
-    const TAG_NAME: &'static str = stringify!(border_right);
-
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.border.right = value;
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.border.top = Val)]
 pub struct border_top;
 
-impl ElementAttr for border_top {
-    type Value = Val;
-
-    const TAG_NAME: &'static str = stringify!(border_top);
-
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.border.top = value;
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.border.bottom = Val)]
 pub struct border_bottom;
 
-impl ElementAttr for border_bottom {
-    type Value = Val;
-
-    const TAG_NAME: &'static str = stringify!(border_bottom);
-
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.border.bottom = value;
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(component = BorderColor)]
 pub struct border_color;
 
-impl ElementAttr for border_color {
-    type Value = BorderColor;
-
-    const TAG_NAME: &'static str = stringify!(border_color);
-
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.insert(value.into());
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.display = ui::Display)]
 pub struct display;
 
-impl ElementAttr for display {
-    type Value = ui::Display;
-
-    const TAG_NAME: &'static str = stringify!(display);
-
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.display = value.into();
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.position_type = PositionType)]
 pub struct position_type;
 
-impl ElementAttr for position_type {
-    type Value = PositionType;
-
-    const TAG_NAME: &'static str = stringify!(position_type);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.position_type = value.into();
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.overflow.x = OverflowAxis)]
 pub struct overflow_x;
 
-impl ElementAttr for overflow_x {
-    type Value = OverflowAxis;
-
-    const TAG_NAME: &'static str = stringify!(overflow_x);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.overflow.x = value;
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.overflow.y = OverflowAxis)]
 pub struct overflow_y;
 
-impl ElementAttr for overflow_y {
-    type Value = OverflowAxis;
-
-    const TAG_NAME: &'static str = stringify!(overflow_y);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.overflow.y = value;
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.direction = Direction)]
 pub struct direction;
 
-impl ElementAttr for direction {
-    type Value = Direction;
-
-    const TAG_NAME: &'static str = stringify!(direction);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.direction = value.into();
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.left = Val)]
 pub struct left;
 
-impl ElementAttr for left {
-    type Value = Val;
-
-    const TAG_NAME: &'static str = stringify!(left);
-
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.left = value.into();
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.right = Val)]
 pub struct right;
 
-impl ElementAttr for right {
-    type Value = Val;
-
-    const TAG_NAME: &'static str = stringify!(right);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.right = value.into();
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.top = Val)]
 pub struct top;
 
-impl ElementAttr for top {
-    type Value = Val;
-
-    const TAG_NAME: &'static str = stringify!(top);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.top = value.into();
-        });
-    }
-}
-
+#[derive(ElementAttr)]
+#[element_attr(style.bottom = Val)]
 pub struct bottom;
 
-impl ElementAttr for bottom {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.width = Val)]
+pub struct width;
 
-    const TAG_NAME: &'static str = stringify!(bottom);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.bottom = value.into();
-        });
-    }
-}
+#[derive(ElementAttr)]
+#[element_attr(style.height = Val)]
+pub struct height;
 
-pub struct width;
+#[derive(ElementAttr)]
+#[element_attr(style.min_width = Val)]
+pub struct min_width;
 
-impl ElementAttr for width {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.min_height = Val)]
+pub struct min_height;
 
-    const TAG_NAME: &'static str = stringify!(width);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.width = value.into();
-        });
-    }
-}
+#[derive(ElementAttr)]
+#[element_attr(style.max_width = Val)]
+pub struct max_width;
 
-pub struct height;
+#[derive(ElementAttr)]
+#[element_attr(style.max_height = Val)]
+pub struct max_height;
 
-impl ElementAttr for height {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.margin.left = Val)]
+pub struct margin_left;
 
-    const TAG_NAME: &'static str = stringify!(height);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.height = value.into();
-        });
-    }
-}
+#[derive(ElementAttr)]
+#[element_attr(style.margin.right = Val)]
+pub struct margin_right;
 
-pub struct min_width;
+#[derive(ElementAttr)]
+#[element_attr(style.margin.top = Val)]
+pub struct margin_top;
 
-impl ElementAttr for min_width {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.margin.bottom = Val)]
+pub struct margin_bottom;
 
-    const TAG_NAME: &'static str = stringify!(min_width);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.min_width = value.into();
-        });
-    }
-}
+#[derive(ElementAttr)]
+#[element_attr(style.padding.left = Val)]
+pub struct padding_left;
 
-pub struct min_height;
+#[derive(ElementAttr)]
+#[element_attr(style.padding.right = Val)]
+pub struct padding_right;
 
-impl ElementAttr for min_height {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.padding.top = Val)]
+pub struct padding_top;
 
-    const TAG_NAME: &'static str = stringify!(min_height);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.min_height = value.into();
-        });
-    }
-}
+#[derive(ElementAttr)]
+#[element_attr(style.padding.bottom = Val)]
+pub struct padding_bottom;
 
-pub struct max_width;
+#[derive(ElementAttr)]
+#[element_attr(style.aspect_ratio = Option<f32>)]
+pub struct aspect_ratio;
 
-impl ElementAttr for max_width {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.align_items = AlignItems)]
+pub struct align_items;
 
-    const TAG_NAME: &'static str = stringify!(max_width);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.max_width = value.into();
-        });
-    }
-}
+#[derive(ElementAttr)]
+#[element_attr(style.justify_items = JustifyItems)]
+pub struct justify_items;
 
-pub struct max_height;
+#[derive(ElementAttr)]
+#[element_attr(style.align_self = AlignSelf)]
+pub struct align_self;
 
-impl ElementAttr for max_height {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.justify_self = JustifySelf)]
+pub struct justify_self;
 
-    const TAG_NAME: &'static str = stringify!(max_height);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.max_height = value.into();
-        });
-    }
-}
+#[derive(ElementAttr)]
+#[element_attr(style.align_content = AlignContent)]
+pub struct align_content;
 
-pub struct margin_left;
+#[derive(ElementAttr)]
+#[element_attr(style.justify_content = JustifyContent)]
+pub struct justify_content;
 
-impl ElementAttr for margin_left {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.flex_direction = FlexDirection)]
+pub struct flex_direction;
 
-    const TAG_NAME: &'static str = stringify!(margin_left);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.margin.left = value;
-        });
-    }
-}
+#[derive(ElementAttr)]
+#[element_attr(style.flex_wrap = FlexWrap)]
+pub struct flex_wrap;
 
-pub struct margin_right;
+#[derive(ElementAttr)]
+#[element_attr(style.flex_grow = f32)]
+pub struct flex_grow;
 
-impl ElementAttr for margin_right {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.flex_shrink = f32)]
+pub struct flex_shrink;
 
-    const TAG_NAME: &'static str = stringify!(margin_right);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.margin.right = value;
-        });
-    }
-}
+#[derive(ElementAttr)]
+#[element_attr(style.flex_basis = Val)]
+pub struct flex_basis;
 
-pub struct margin_top;
+#[derive(ElementAttr)]
+#[element_attr(style.column_gap = Val)]
+pub struct column_gap;
 
-impl ElementAttr for margin_top {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.row_gap = Val)]
+pub struct row_gap;
 
-    const TAG_NAME: &'static str = stringify!(margin_top);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.margin.top = value;
-        });
-    }
-}
+#[derive(ElementAttr)]
+#[element_attr(component = Visibility)]
+pub struct visibility;
 
-pub struct margin_bottom;
+pub struct transation;
 
-impl ElementAttr for margin_bottom {
-    type Value = Val;
+impl ElementAttr for transation {
+    type Value = Vec3;
 
-    const TAG_NAME: &'static str = stringify!(margin_bottom);
+    const TAG_NAME: &'static str = stringify!(transation);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.margin.bottom = value;
-        });
+        let value = value.into();
+        if let Some(mut tf) = context.entity_ref.get_mut::<Transform>() {
+            tf.translation = value;
+        } else {
+            warn!("no found Transform component!");
+        }
     }
 }
 
-pub struct padding_left;
-
-impl ElementAttr for padding_left {
-    type Value = Val;
+/// Offset of the node added after the layout, set by `translate_x` and `translate_y`.
+/// Like css `translate`, percentages are relative to the node's own size.
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component, Default)]
+pub struct UiTranslation {
+    pub x: Val,
+    pub y: Val,
+}
 
-    const TAG_NAME: &'static str = stringify!(padding_left);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.padding.left = value;
-        });
+impl UiTranslation {
+    pub fn resolve(&self, size: Vec2) -> Vec2 {
+        let resolve = |val: Val, size: f32| match val {
+            Val::Px(n) => n,
+            Val::Percent(n) => size * n / 100.0,
+            _ => 0.0,
+        };
+        Vec2::new(resolve(self.x, size.x), resolve(self.y, size.y))
     }
 }
 
-pub struct padding_right;
-
-impl ElementAttr for padding_right {
-    type Value = Val;
-
-    const TAG_NAME: &'static str = stringify!(padding_right);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.padding.right = value;
-        });
+fn update_ui_translation(entity_ref: &mut EntityMut, f: impl FnOnce(&mut UiTranslation)) {
+    if let Some(mut translation) = entity_ref.get_mut::<UiTranslation>() {
+        f(&mut translation);
+    } else {
+        let mut translation = UiTranslation::default();
+        f(&mut translation);
+        entity_ref.insert(translation);
     }
 }
 
-pub struct padding_top;
+pub struct translate_x;
 
-impl ElementAttr for padding_top {
+impl ElementAttr for translate_x {
     type Value = Val;
 
-    const TAG_NAME: &'static str = stringify!(padding_top);
+    const TAG_NAME: &'static str = stringify!(translate_x);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.padding.top = value;
-        });
+        let value = value.into();
+        update_ui_translation(context.entity_ref, |translation| translation.x = value);
     }
 }
 
-pub struct padding_bottom;
+pub struct translate_y;
 
-impl ElementAttr for padding_bottom {
+impl ElementAttr for translate_y {
     type Value = Val;
 
-    const TAG_NAME: &'static str = stringify!(padding_bottom);
+    const TAG_NAME: &'static str = stringify!(translate_y);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            let value = value.into();
-            style.padding.bottom = value;
-        });
+        let value = value.into();
+        update_ui_translation(context.entity_ref, |translation| translation.y = value);
     }
 }
 
-pub struct aspect_ratio;
+pub struct rotation;
 
-impl ElementAttr for aspect_ratio {
-    type Value = Option<f32>;
+impl ElementAttr for rotation {
+    type Value = Quat;
 
-    const TAG_NAME: &'static str = stringify!(aspect_ratio);
+    const TAG_NAME: &'static str = stringify!(rotation);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.aspect_ratio = value.into();
-        });
+        let value = value.into();
+        if let Some(mut tf) = context.entity_ref.get_mut::<Transform>() {
+            tf.rotation = value;
+        } else {
+            warn!("no found Transform component!");
+        }
     }
 }
 
-pub struct align_items;
+pub struct scale;
 
-impl ElementAttr for align_items {
-    type Value = AlignItems;
+impl ElementAttr for scale {
+    type Value = Vec3;
 
-    const TAG_NAME: &'static str = stringify!(align_items);
+    const TAG_NAME: &'static str = stringify!(scale);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.align_items = value.into();
-        });
+        let value = value.into();
+        if let Some(mut tf) = context.entity_ref.get_mut::<Transform>() {
+            tf.scale = value;
+        } else {
+            warn!("no found Transform component!");
+        }
     }
 }
 
-pub struct justify_items;
+pub struct text_color;
 
-impl ElementAttr for justify_items {
-    type Value = JustifyItems;
+impl ElementAttr for text_color {
+    type Value = Color;
 
-    const TAG_NAME: &'static str = stringify!(justify_items);
+    const TAG_NAME: &'static str = stringify!(text_color);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.justify_items = value.into();
+        let value = value.into();
+        set_text_value(context, |text_schema_type, entity_ref| {
+            text_schema_type.set_text_color(entity_ref, value);
         });
     }
 }
 
-pub struct align_self;
+pub struct font_size;
 
-impl ElementAttr for align_self {
-    type Value = AlignSelf;
+impl ElementAttr for font_size {
+    type Value = f32;
 
-    const TAG_NAME: &'static str = stringify!(align_self);
+    const TAG_NAME: &'static str = stringify!(font_size);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.align_self = value.into();
+        let value = value.into();
+        set_text_value(context, |text_schema_type, entity_ref| {
+            text_schema_type.set_font_size(entity_ref, value);
         });
     }
 }
 
-pub struct justify_self;
+pub struct text_linebreak;
 
-impl ElementAttr for justify_self {
-    type Value = JustifySelf;
+impl ElementAttr for text_linebreak {
+    type Value = BreakLineOn;
 
-    const TAG_NAME: &'static str = stringify!(justify_self);
+    const TAG_NAME: &'static str = stringify!(text_linebreak);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.justify_self = value.into();
+        let value = value.into();
+        set_text_value(context, |text_schema_type, entity_ref| {
+            text_schema_type.set_text_linebreak(entity_ref, value);
         });
     }
 }
 
-pub struct align_content;
+pub struct text_align;
 
-impl ElementAttr for align_content {
-    type Value = AlignContent;
+impl ElementAttr for text_align {
+    type Value = TextAlignment;
 
-    const TAG_NAME: &'static str = stringify!(align_content);
+    const TAG_NAME: &'static str = stringify!(text_align);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.align_content = value.into();
+        let value = value.into();
+        set_text_value(context, |text_schema_type, entity_ref| {
+            text_schema_type.set_text_align(entity_ref, value);
         });
     }
 }
 
-pub struct justify_content;
+pub struct font;
 
-impl ElementAttr for justify_content {
-    type Value = JustifyContent;
+impl ElementAttr for font {
+    type Value = Handle<Font>;
 
-    const TAG_NAME: &'static str = stringify!(justify_content);
+    const TAG_NAME: &'static str = stringify!(font);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.justify_content = value.into();
+        let value = value.into();
+        set_text_value(context, |text_schema_type, entity_ref| {
+            text_schema_type.set_font(entity_ref, value.clone());
         });
     }
 }
 
-pub struct flex_direction;
+pub struct draggable;
 
-impl ElementAttr for flex_direction {
-    type Value = FlexDirection;
+impl ElementAttr for draggable {
+    type Value = bool;
 
-    const TAG_NAME: &'static str = stringify!(flex_direction);
+    const TAG_NAME: &'static str = stringify!(draggable);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.flex_direction = value.into();
-        });
+        if value.into() {
+            context.entity_ref.insert(Draggable);
+        } else {
+            context.entity_ref.remove::<Draggable>();
+        }
     }
 }
 
-pub struct flex_wrap;
+pub struct disabled;
 
-impl ElementAttr for flex_wrap {
-    type Value = FlexWrap;
+impl ElementAttr for disabled {
+    type Value = bool;
 
-    const TAG_NAME: &'static str = stringify!(flex_wrap);
+    const TAG_NAME: &'static str = stringify!(disabled);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.flex_wrap = value.into();
-        });
+        let entity_ref = &mut *context.entity_ref;
+        if value.into() {
+            if !entity_ref.contains::<Disabled>() {
+                let previous = entity_ref.get::<Pickable>().cloned();
+                entity_ref.insert(PickableBeforeDisabled(previous));
+            }
+            entity_ref.insert((
+                Disabled,
+                Pickable {
+                    should_block_lower: true,
+                    should_emit_events: false,
+                },
+            ));
+            let entity = entity_ref.id();
+            entity_ref.world_scope(|world| {
+                if let Some(mut focus) = world.get_resource_mut::<Focus>() {
+                    if focus.0 == Some(entity) {
+                        focus.0 = None;
+                    }
+                }
+            });
+        } else if entity_ref.contains::<Disabled>() {
+            let previous = entity_ref.take::<PickableBeforeDisabled>().and_then(|n| n.0);
+            entity_ref.remove::<(Disabled, Pickable)>();
+            if let Some(pickable) = previous {
+                entity_ref.insert(pickable);
+            }
+        }
+        update_element_states(context);
     }
 }
 
-pub struct flex_grow;
+pub struct checked;
 
-impl ElementAttr for flex_grow {
-    type Value = f32;
+impl ElementAttr for checked {
+    type Value = bool;
 
-    const TAG_NAME: &'static str = stringify!(flex_grow);
+    const TAG_NAME: &'static str = stringify!(checked);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.flex_grow = value.into();
-        });
+        if value.into() {
+            context.entity_ref.insert(Checked);
+        } else {
+            context.entity_ref.remove::<Checked>();
+        }
+        update_element_states(context);
     }
 }
 
-pub struct flex_shrink;
-
-impl ElementAttr for flex_shrink {
-    type Value = f32;
-
-    const TAG_NAME: &'static str = stringify!(flex_shrink);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.flex_shrink = value.into();
-        });
-    }
+/// Components inserted by the `components` attr, removed when it changes
+#[derive(Component, Reflect, Default, Clone)]
+#[reflect(Component, Default)]
+pub struct AttachedComponents {
+    pub type_names: Vec<String>,
+    /// components the element had before they were replaced by attached ones, restored on removal
+    pub replaced: UiComponents,
 }
 
-pub struct flex_basis;
+pub struct components;
 
-impl ElementAttr for flex_basis {
-    type Value = Val;
+impl ElementAttr for components {
+    type Value = UiComponents;
 
-    const TAG_NAME: &'static str = stringify!(flex_basis);
+    const TAG_NAME: &'static str = stringify!(components);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.flex_basis = value.into();
-        });
+        let value = value.into();
+        let type_registry = context.type_registry.clone();
+        let type_registry = type_registry.read();
+        if let Some(attached) = context.entity_ref.take::<AttachedComponents>() {
+            for type_name in attached.type_names {
+                if let Some(reflect_component) = type_registry
+                    .get_with_name(&type_name)
+                    .and_then(|n| n.data::<ReflectComponent>())
+                {
+                    reflect_component.remove(context.entity_ref);
+                }
+            }
+            for component in attached.replaced.0 {
+                if let Some(reflect_component) = type_registry
+                    .get_with_name(component.type_name())
+                    .and_then(|n| n.data::<ReflectComponent>())
+                {
+                    reflect_component.insert(context.entity_ref, component.as_reflect());
+                }
+            }
+        }
+        let mut attached: Vec<String> = vec![];
+        let mut replaced = UiComponents::default();
+        for component in value.0.iter() {
+            let type_name = component.type_name();
+            let Some(reflect_component) = type_registry
+                .get_with_name(type_name)
+                .and_then(|n| n.data::<ReflectComponent>())
+            else {
+                warn!("component {} is not registered with #[reflect(Component)]", type_name);
+                continue;
+            };
+            if !attached.iter().any(|n| n == type_name) {
+                if let Some(existing) = reflect_component.reflect_mut(context.entity_ref) {
+                    replaced.0.push(existing.clone_value());
+                }
+                attached.push(type_name.to_string());
+            }
+            reflect_component.insert(context.entity_ref, component.as_reflect());
+        }
+        if !attached.is_empty() {
+            context.entity_ref.insert(AttachedComponents {
+                type_names: attached,
+                replaced,
+            });
+        }
     }
 }
 
-pub struct column_gap;
+#[derive(ElementAttr)]
+#[element_attr(style.grid_template_columns = Vec<RepeatedGridTrack>)]
+pub struct grid_template_columns;
 
-impl ElementAttr for column_gap {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.grid_template_rows = Vec<RepeatedGridTrack>)]
+pub struct grid_template_rows;
 
-    const TAG_NAME: &'static str = stringify!(column_gap);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.column_gap = value.into();
-        });
-    }
-}
-
-pub struct row_gap;
+#[derive(ElementAttr)]
+#[element_attr(style.grid_auto_flow = GridAutoFlow)]
+pub struct grid_auto_flow;
 
-impl ElementAttr for row_gap {
-    type Value = Val;
+#[derive(ElementAttr)]
+#[element_attr(style.grid_auto_columns = Vec<GridTrack>)]
+pub struct grid_auto_columns;
 
-    const TAG_NAME: &'static str = stringify!(row_gap);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.try_set_style(|style| {
-            style.row_gap = value.into();
-        });
-    }
-}
+#[derive(ElementAttr)]
+#[element_attr(style.grid_auto_rows = Vec<GridTrack>)]
+pub struct grid_auto_rows;
 
-pub struct visibility;
+#[derive(ElementAttr)]
+#[element_attr(style.grid_row = GridPlacement)]
+pub struct grid_row;
 
-impl ElementAttr for visibility {
-    type Value = Visibility;
+#[derive(ElementAttr)]
+#[element_attr(style.grid_column = GridPlacement)]
+pub struct grid_column;
 
-    const TAG_NAME: &'static str = stringify!(visibility);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        context.entity_ref.insert(value.into());
-    }
-}
+/// Sets one line of a placement and keeps the other one, so `col-start-2 col-end-4` combine
+macro_rules! grid_line_attr {
+    ($name:ident, $field:ident, $with:ident) => {
+        pub struct $name;
 
-pub struct transation;
+        impl ElementAttr for $name {
+            type Value = i64;
 
-impl ElementAttr for transation {
-    type Value = Vec3;
+            const TAG_NAME: &'static str = stringify!($name);
 
-    const TAG_NAME: &'static str = stringify!(transation);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        let value = value.into();
-        if let Some(mut tf) = context.entity_ref.get_mut::<Transform>() {
-            tf.translation = value;
-        } else {
-            warn!("no found Transform component!");
+            fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+                let line = grid_line(value.into());
+                context
+                    .entity_ref
+                    .try_set_style(|style| style.$field = $with(style.$field, line));
+            }
         }
-    }
+    };
 }
 
-pub struct rotation;
-
-impl ElementAttr for rotation {
-    type Value = Quat;
-
-    const TAG_NAME: &'static str = stringify!(rotation);
-    fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
-        let value = value.into();
-        if let Some(mut tf) = context.entity_ref.get_mut::<Transform>() {
-            tf.rotation = value;
-        } else {
-            warn!("no found Transform component!");
-        }
-    }
-}
+grid_line_attr!(grid_row_start, grid_row, with_grid_start);
+grid_line_attr!(grid_row_end, grid_row, with_grid_end);
+grid_line_attr!(grid_column_start, grid_column, with_grid_start);
+grid_line_attr!(grid_column_end, grid_column, with_grid_end);
 
-pub struct scale;
+pub struct transition;
 
-impl ElementAttr for scale {
-    type Value = Vec3;
+impl ElementAttr for transition {
+    type Value = TransitionShorthand;
 
-    const TAG_NAME: &'static str = stringify!(scale);
+    const TAG_NAME: &'static str = stringify!(transition);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
         let value = value.into();
-        if let Some(mut tf) = context.entity_ref.get_mut::<Transform>() {
-            tf.scale = value;
-        } else {
-            warn!("no found Transform component!");
-        }
+        update_transition(context.entity_ref, |transition| {
+            if let Some(properties) = value.properties {
+                transition.properties = properties;
+            }
+            if let Some(duration) = value.duration {
+                transition.duration = duration / 1000.0;
+            }
+            if let Some(easing) = value.easing {
+                transition.easing = easing;
+            }
+            if let Some(delay) = value.delay {
+                transition.delay = delay / 1000.0;
+            }
+        });
     }
 }
 
-pub struct text_color;
+/// In milliseconds
+pub struct transition_duration;
 
-impl ElementAttr for text_color {
-    type Value = Color;
+impl ElementAttr for transition_duration {
+    type Value = f32;
 
-    const TAG_NAME: &'static str = stringify!(text_color);
+    const TAG_NAME: &'static str = stringify!(transition_duration);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
         let value = value.into();
-        set_text_value(context, |text_schema_type, entity_ref| {
-            text_schema_type.set_text_color(entity_ref, value);
+        update_transition(context.entity_ref, |transition| {
+            transition.duration = value / 1000.0;
         });
     }
+
+    fn set_to_default_value(&self, context: &mut SetAttrValueContext) {
+        self.set_value(context, UiTransition::DEFAULT_DURATION_MS);
+    }
 }
 
-pub struct font_size;
+pub struct transition_timing;
 
-impl ElementAttr for font_size {
-    type Value = f32;
+impl ElementAttr for transition_timing {
+    type Value = Easing;
 
-    const TAG_NAME: &'static str = stringify!(font_size);
+    const TAG_NAME: &'static str = stringify!(transition_timing);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
         let value = value.into();
-        set_text_value(context, |text_schema_type, entity_ref| {
-            text_schema_type.set_font_size(entity_ref, value);
+        update_transition(context.entity_ref, |transition| {
+            transition.easing = value;
         });
     }
 }
 
-pub struct text_linebreak;
+/// In milliseconds
+pub struct transition_delay;
 
-impl ElementAttr for text_linebreak {
-    type Value = BreakLineOn;
+impl ElementAttr for transition_delay {
+    type Value = f32;
 
-    const TAG_NAME: &'static str = stringify!(text_linebreak);
+    const TAG_NAME: &'static str = stringify!(transition_delay);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
         let value = value.into();
-        set_text_value(context, |text_schema_type, entity_ref| {
-            text_schema_type.set_text_linebreak(entity_ref, value);
+        update_transition(context.entity_ref, |transition| {
+            transition.delay = value / 1000.0;
         });
     }
 }
 
-pub struct text_align;
+/// `"pulse 2s ease-in-out infinite alternate"`, restarts when the value changes
+pub struct animation;
 
-impl ElementAttr for text_align {
-    type Value = TextAlignment;
+impl ElementAttr for animation {
+    type Value = AnimationSpec;
 
-    const TAG_NAME: &'static str = stringify!(text_align);
+    const TAG_NAME: &'static str = stringify!(animation);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
         let value = value.into();
-        set_text_value(context, |text_schema_type, entity_ref| {
-            text_schema_type.set_text_align(entity_ref, value);
-        });
+        if context
+            .entity_ref
+            .get::<UiAnimation>()
+            .is_some_and(|n| n.spec == value)
+        {
+            return;
+        }
+        restore_animation_base(context.entity_ref);
+        if value.name.is_empty() {
+            context.entity_ref.remove::<UiAnimation>();
+        } else {
+            context.entity_ref.insert(UiAnimation::new(value));
+        }
     }
 }
 
-pub struct font;
+/// Links the element to a `use_animation` handle
+pub struct animation_handle;
 
-impl ElementAttr for font {
-    type Value = Handle<Font>;
+impl ElementAttr for animation_handle {
+    type Value = AnimationHandle;
 
-    const TAG_NAME: &'static str = stringify!(font);
+    const TAG_NAME: &'static str = stringify!(animation_handle);
     fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
         let value = value.into();
-        set_text_value(context, |text_schema_type, entity_ref| {
-            text_schema_type.set_font(entity_ref, value.clone());
-        });
+        if value == AnimationHandle::default() {
+            context.entity_ref.remove::<AnimationHandle>();
+        } else {
+            context.entity_ref.insert(value);
+        }
     }
 }
@@ -11,7 +11,7 @@ pub use composite_attrs::*;
 
 use crate::{attrs_trait_define, composite_attrs_trait_define, ElementCompositeAttr, ElementTypeUnTyped, get_element_type};
 use crate::ecs_fns::StyleEntityExt;
-use crate::element_core::ElementAttr;
+use crate::element_core::{ElementAttr, ElementRegistry};
 use crate::entity_extra_data::{EntitiesExtraData, EntityExtraData};
 
 mod attr_values;
@@ -30,6 +30,7 @@ pub struct SetAttrValueContext<'w, 'e> {
     pub entities_extra_data: &'e mut EntitiesExtraData,
     pub entity_ref: &'w mut EntityMut<'w>,
     pub type_registry: AppTypeRegistry,
+    pub element_registry: ElementRegistry,
 }
 
 impl<'w, 'e> SetAttrValueContext<'w, 'e> {
@@ -44,7 +45,8 @@ impl<'w, 'e> SetAttrValueContext<'w, 'e> {
     }
 
     pub fn element_type(&mut self) -> &'static dyn ElementTypeUnTyped {
-        get_element_type(self.entity_extra_data().schema_name)
+        let schema_name = self.entity_extra_data().schema_name;
+        get_element_type(&self.element_registry, schema_name)
     }
 }
 
@@ -96,15 +98,38 @@ attrs_trait_define!(CommonAttrs;0;
     flex_basis,
     column_gap,
     row_gap,
+    grid_template_columns,
+    grid_template_rows,
+    grid_auto_flow,
+    grid_auto_columns,
+    grid_auto_rows,
+    grid_row,
+    grid_column,
+    grid_row_start,
+    grid_row_end,
+    grid_column_start,
+    grid_column_end,
     visibility,
     transation,
+    translate_x,
+    translate_y,
     rotation,
     scale,
     text_color,
     font_size,
     text_linebreak,
     text_align,
-    font
+    font,
+    draggable,
+    disabled,
+    checked,
+    components,
+    transition,
+    transition_duration,
+    transition_timing,
+    transition_delay,
+    animation,
+    animation_handle
 );
 
 composite_attrs_trait_define!(CommonCompositeAttrs;
@@ -1,10 +1,11 @@
 use crate::dom_commands::DomAttributeValue;
 use crate::element_attrs::SetAttrValueContext;
-use crate::element_core::AttrValue;
+use crate::element_core::{AttrValue, CUSTOM_ATTR_INDEX};
 use crate::entity_extra_data::AttrIndex;
 use crate::smallbox::S1;
 use crate::DioxusAttributeDescription;
 use crate::SmallBox;
+use crate::smallbox;
 
 pub trait HasIndex {
     const INDEX: AttrIndex;
@@ -17,13 +18,16 @@ pub trait ElementAttrUntyped: Send + Sync {
 
     fn attribute_description(&self) -> DioxusAttributeDescription;
 
-    fn index(&self) -> u8;
+    fn index(&self) -> AttrIndex;
 
     fn set_by_attr_value(&self, context: &mut SetAttrValueContext, value: DomAttributeValue);
     fn set_dyn_value(&self, context: &mut SetAttrValueContext, value: SmallBox<dyn AttrValue, S1>);
 
     fn set_to_default_value(&self, context: &mut SetAttrValueContext);
 
+    /// Value of the attr without setting it, `None` when it doesn't convert
+    fn parse_dyn_value(&self, value: DomAttributeValue) -> Option<SmallBox<dyn AttrValue, S1>>;
+
     fn set_dyn_value_in_class(
         &self,
         context: &mut SetAttrValueContext,
@@ -56,7 +60,7 @@ where
     }
 
     #[inline]
-    fn index(&self) -> u8 {
+    fn index(&self) -> AttrIndex {
         <T as HasIndex>::INDEX
     }
 
@@ -75,6 +79,13 @@ where
         self.set_to_default_value(context)
     }
 
+    #[inline]
+    fn parse_dyn_value(&self, value: DomAttributeValue) -> Option<SmallBox<dyn AttrValue, S1>> {
+        let value = Into::<Option<T::Value>>::into(value)?;
+        let value: SmallBox<dyn AttrValue, S1> = smallbox!(value);
+        Some(value)
+    }
+
     #[inline]
     fn set_dyn_value_in_class(
         &self,
@@ -133,12 +144,28 @@ where
 
     #[inline]
     fn set_value_in_class(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+        let Some(index) = self.attr_index(context) else {
+            self.set_value(context, value);
+            return;
+        };
         let entity_extra_data = context.entity_extra_data();
-        if !entity_extra_data.is_set_attr(Self::INDEX) {
-            entity_extra_data.set_class_attr(Self::INDEX, true);
+        if !entity_extra_data.is_set_attr(index) {
+            entity_extra_data.set_class_attr(index, true);
             self.set_value(context, value);
         }
     }
+
+    /// Index of the attr on the element of `context`, attrs registered at runtime are looked up
+    /// in the `ElementRegistry`, `None` when it isn't registered on that element
+    fn attr_index(&self, context: &mut SetAttrValueContext) -> Option<AttrIndex> {
+        if Self::INDEX != CUSTOM_ATTR_INDEX {
+            return Some(Self::INDEX);
+        }
+        let schema_name = context.entity_extra_data().schema_name;
+        context
+            .element_registry
+            .custom_attr_index(schema_name, Self::NAME)
+    }
 }
 
 pub enum SetAttrValueError {}
@@ -1,14 +1,187 @@
+use std::cell::Cell;
 use std::{ops::Deref, rc::Rc};
 
-use bevy::prelude::{Res, World};
-use bevy_mod_picking::prelude::{EntityEvent, ListenerInput, On};
+use bevy::ecs::system::Command;
+use bevy::prelude::{warn, Commands, Component, Entity, EventReader, Parent, Rect, World};
+use bevy_mod_picking::prelude::EntityEvent;
 use dioxus::core::ElementId;
 
 use crate::apc::{self};
+use crate::entity_extra_data::Disabled;
+use crate::ui_events::ui_node_rect;
 use crate::vdm_data::VDomData;
+use crate::{DomApcSender, EcsSender};
+
+pub trait DomEvent: EntityEvent + DomEventData + Clone {
+    const HAS_DEFAULT_ACTION: bool = false;
 
-pub trait DomEvent: EntityEvent + Clone {
     fn dom_event_name() -> &'static str;
+
+    fn apply_default_action(&self, _world: &mut World) {}
+}
+
+/// Converts an ecs event into the data handed to dioxus handlers
+pub trait DomEventData {
+    type Data: Send + 'static;
+    /// Events that never reach elements inside a `disabled` element
+    const BLOCKED_BY_DISABLED: bool = false;
+
+    /// Events that aren't sent to elements at all, e.g. drag events of non draggable elements
+    fn is_dispatched(&self, _world: &World) -> bool {
+        true
+    }
+
+    fn event_data(&self, world: &World) -> Self::Data;
+}
+
+#[macro_export]
+macro_rules! impl_self_event_data {
+    ($($type:ty),*) => {
+        $(
+            impl $crate::element_core::DomEventData for $type {
+                type Data = Self;
+
+                fn event_data(&self, _world: &bevy::prelude::World) -> Self::Data {
+                    self.clone()
+                }
+            }
+        )*
+    };
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventPhase {
+    Capturing,
+    AtTarget,
+    Bubbling,
+}
+
+/// Event object passed to dioxus handlers, shared by every listener along the propagation path
+pub struct UiEvent<T> {
+    data: T,
+    target: Option<ElementId>,
+    current_target: Cell<Option<ElementId>>,
+    current_target_rect: Cell<Option<Rect>>,
+    phase: Cell<EventPhase>,
+    passive: Cell<bool>,
+    propagation_stopped: Cell<bool>,
+    default_prevented: Cell<bool>,
+}
+
+impl<T> UiEvent<T> {
+    pub fn new(data: T, target: Option<ElementId>) -> Self {
+        Self {
+            data,
+            target,
+            current_target: Cell::new(None),
+            current_target_rect: Cell::new(None),
+            phase: Cell::new(EventPhase::AtTarget),
+            passive: Cell::new(false),
+            propagation_stopped: Cell::new(false),
+            default_prevented: Cell::new(false),
+        }
+    }
+
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    pub fn target(&self) -> Option<ElementId> {
+        self.target
+    }
+
+    pub fn current_target(&self) -> Option<ElementId> {
+        self.current_target.get()
+    }
+
+    /// layout rect of the current target in logical pixels, relative to the window's top left
+    pub fn current_target_rect(&self) -> Option<Rect> {
+        self.current_target_rect.get()
+    }
+
+    pub fn phase(&self) -> EventPhase {
+        self.phase.get()
+    }
+
+    pub fn stop_propagation(&self) {
+        self.propagation_stopped.set(true);
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation_stopped.get()
+    }
+
+    pub fn prevent_default(&self) {
+        if self.passive.get() {
+            warn!("prevent_default is ignored inside a passive event listener!");
+            return;
+        }
+        self.default_prevented.set(true);
+    }
+
+    pub fn is_default_prevented(&self) -> bool {
+        self.default_prevented.get()
+    }
+}
+
+impl<T> Deref for UiEvent<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ListenerOptions {
+    pub capture: bool,
+    pub passive: bool,
+}
+
+impl ListenerOptions {
+    /// split a listener name like `click_capture_passive` into `click` and its options
+    pub fn parse(name: &str) -> (&str, Self) {
+        let mut options = Self::default();
+        let mut name = name;
+        if let Some(n) = name.strip_suffix("_passive") {
+            name = n;
+            options.passive = true;
+        }
+        if let Some(n) = name.strip_suffix("_capture") {
+            name = n;
+            options.capture = true;
+        }
+        (name, options)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DomEventListener {
+    pub event_name: &'static str,
+    pub name: String,
+    pub options: ListenerOptions,
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct DomEventListeners {
+    pub element_id: ElementId,
+    pub listeners: Vec<DomEventListener>,
+}
+
+struct DispatchStep {
+    element_id: ElementId,
+    name: String,
+    phase: EventPhase,
+    passive: bool,
+    rect: Option<Rect>,
+}
+
+pub struct ApplyDefaultAction<T: DomEvent>(pub T);
+
+impl<T: DomEvent> Command for ApplyDefaultAction<T> {
+    fn apply(self, world: &mut World) {
+        self.0.apply_default_action(world);
+    }
 }
 
 #[doc(hidden)]
@@ -31,26 +204,143 @@ impl<T> EventReturn<AsyncMarker> for T
     }
 }
 
-pub fn listen_dom_event<T: DomEvent>(world: &mut World, element_id: ElementId) {
+pub fn listen_dom_event<T: DomEvent>(world: &mut World, element_id: ElementId, name: &str) {
     let vdom_data = world.resource::<VDomData>();
     let entity = vdom_data.element_id_to_entity[&element_id];
+    let (_, options) = ListenerOptions::parse(name);
+    let listener = DomEventListener {
+        event_name: <T as DomEvent>::dom_event_name(),
+        name: name.to_string(),
+        options,
+    };
     let mut entity_ref = world.entity_mut(entity);
-    entity_ref.insert(On::<T>::run(
-        move |apc_sender: Res<crate::DomApcSender>, event: Res<ListenerInput<T>>| {
-            let name = <T as DomEvent>::dom_event_name();
-            let data: &T = event.deref();
-            let data = data.clone();
-            apc::call(&apc_sender.0, move |vdom| {
-                vdom.handle_event(&name, Rc::new(data), element_id, false);
-            });
-        },
-    ));
-}
-
-pub fn unlisten_dom_event<T: DomEvent>(world: &mut World, element_id: ElementId) {
+    if let Some(mut listeners) = entity_ref.get_mut::<DomEventListeners>() {
+        if listeners.listeners.iter().all(|n| n.name != name) {
+            listeners.listeners.push(listener);
+        }
+    } else {
+        entity_ref.insert(DomEventListeners {
+            element_id,
+            listeners: vec![listener],
+        });
+    }
+}
+
+pub fn unlisten_dom_event<T: DomEvent>(world: &mut World, element_id: ElementId, name: &str) {
     let vdom_data: &VDomData = world.resource::<VDomData>();
     let entity = vdom_data.element_id_to_entity[&element_id];
-    world.entity_mut(entity).remove::<On<T>>();
+    let mut entity_ref = world.entity_mut(entity);
+    let Some(mut listeners) = entity_ref.get_mut::<DomEventListeners>() else {
+        return;
+    };
+    listeners.listeners.retain(|n| n.name != name);
+    if listeners.listeners.is_empty() {
+        entity_ref.remove::<DomEventListeners>();
+    }
+}
+
+// Capture listeners run from the root down to the target, bubble listeners from the target up.
+// All of them are called in one vdom call, so `stop_propagation` can cut the path short.
+fn is_inside_disabled(world: &World, entity: Entity) -> bool {
+    let mut entity = Some(entity);
+    while let Some(current) = entity {
+        if world.get::<Disabled>(current).is_some() {
+            return true;
+        }
+        entity = world.get::<Parent>(current).map(|n| n.get());
+    }
+    false
+}
+
+pub fn dispatch_dom_events<T: DomEvent>(
+    mut events: EventReader<T>,
+    world: &World,
+    mut commands: Commands,
+) {
+    let event_name = <T as DomEvent>::dom_event_name();
+    for event in events.iter() {
+        let target = event.target();
+        if <T as DomEventData>::BLOCKED_BY_DISABLED && is_inside_disabled(world, target) {
+            continue;
+        }
+        if !event.is_dispatched(world) {
+            continue;
+        }
+        let mut capture_steps = vec![];
+        let mut bubble_steps = vec![];
+        let mut entity = Some(target);
+        while let Some(current) = entity {
+            if let Some(element_listeners) = world.get::<DomEventListeners>(current) {
+                for listener in element_listeners
+                    .listeners
+                    .iter()
+                    .filter(|n| n.event_name == event_name)
+                {
+                    let (steps, phase) = if current == target {
+                        if listener.options.capture {
+                            (&mut capture_steps, EventPhase::AtTarget)
+                        } else {
+                            (&mut bubble_steps, EventPhase::AtTarget)
+                        }
+                    } else if listener.options.capture {
+                        (&mut capture_steps, EventPhase::Capturing)
+                    } else if event.can_bubble() {
+                        (&mut bubble_steps, EventPhase::Bubbling)
+                    } else {
+                        continue;
+                    };
+                    steps.push(DispatchStep {
+                        element_id: element_listeners.element_id,
+                        name: listener.name.clone(),
+                        phase,
+                        passive: listener.options.passive,
+                        rect: ui_node_rect(world, current),
+                    });
+                }
+            }
+            entity = world.get::<Parent>(current).map(|n| n.get());
+        }
+        capture_steps.reverse();
+        capture_steps.append(&mut bubble_steps);
+        let steps = capture_steps;
+
+        let has_default_action = <T as DomEvent>::HAS_DEFAULT_ACTION;
+        let wait_default_action = has_default_action && steps.iter().any(|n| !n.passive);
+        if has_default_action && !wait_default_action {
+            commands.add(ApplyDefaultAction(event.clone()));
+        }
+        if steps.is_empty() {
+            continue;
+        }
+
+        let target = world.resource::<VDomData>().get_element_id_by_entity(target);
+        let default_action_event = wait_default_action.then(|| event.clone());
+        let data = event.event_data(world);
+        apc::call(&world.resource::<DomApcSender>().0, move |vdom| {
+            let ui_event = Rc::new(UiEvent::new(data, target));
+            let mut current_target = None;
+            for step in steps {
+                if current_target != Some(step.element_id) && ui_event.is_propagation_stopped() {
+                    break;
+                }
+                current_target = Some(step.element_id);
+                ui_event.current_target.set(current_target);
+                ui_event.current_target_rect.set(step.rect);
+                ui_event.phase.set(step.phase);
+                ui_event.passive.set(step.passive);
+                vdom.handle_event(&step.name, ui_event.clone(), step.element_id, false);
+            }
+            let Some(event) = default_action_event else {
+                return;
+            };
+            if ui_event.is_default_prevented() {
+                return;
+            }
+            if let Some(ecs_sender) = vdom.base_scope().has_context::<EcsSender>() {
+                ecs_sender.send_cmd(ApplyDefaultAction(event));
+            }
+        });
+    }
 }
 
 #[macro_export]
@@ -58,49 +348,90 @@ macro_rules! impl_events {
     (
         $(
             $( #[$attr:meta] )*
-            $name:ident: $data:ty
+            $name:ident: $data:ty $(=> $default_action:expr)?
         ),*
     ) => {
         use bevy_mod_picking::events::*;
-        use crate::element_core::{listen_dom_event,unlisten_dom_event};
+        use crate::element_core::{dispatch_dom_events, listen_dom_event, unlisten_dom_event, ListenerOptions, UiEvent};
         pub fn listen_dom_event_by_name(world: &mut bevy::prelude::World, element_id: dioxus::core::ElementId, name: &str) {
-
-            match name {
+            let (event_name, _) = ListenerOptions::parse(name);
+            match event_name {
                 $(
-                a if a == <$data as crate::element_core::DomEvent>::dom_event_name() => listen_dom_event::<$data>(world, element_id),
+                a if a == <$data as crate::element_core::DomEvent>::dom_event_name() => listen_dom_event::<$data>(world, element_id, name),
                 )*
                 _ => {}
             }
         }
         pub fn unlisten_dom_event_by_name(world: &mut bevy::prelude::World, element_id: dioxus::core::ElementId, name: &str) {
-
-            match name {
+            let (event_name, _) = ListenerOptions::parse(name);
+            match event_name {
                 $(
-                a if a == <$data as crate::element_core::DomEvent>::dom_event_name() => unlisten_dom_event::<$data>(world, element_id),
+                a if a == <$data as crate::element_core::DomEvent>::dom_event_name() => unlisten_dom_event::<$data>(world, element_id, name),
                 )*
                 _ => {}
             }
         }
+        pub fn add_dom_event_systems(app: &mut bevy::prelude::App) {
+            $(
+                app.add_systems(bevy::prelude::PostUpdate, dispatch_dom_events::<$data>);
+            )*
+        }
 
         $(
             impl crate::element_core::DomEvent for $data {
+                $(
+                const HAS_DEFAULT_ACTION: bool = true;
+
+                fn apply_default_action(&self, world: &mut bevy::prelude::World) {
+                    ($default_action)(self, world)
+                }
+                )?
+
                 fn dom_event_name() -> &'static str {
                     &stringify!($name)[2..]
                 }
             }
 
-            $( #[$attr] )*
-            #[inline]
-            pub fn $name<'a, E: crate::element_core::EventReturn<T>, T>(_cx: &'a dioxus::core::ScopeState, mut _f: impl FnMut(dioxus::core::Event<$data>) -> E + 'a) -> dioxus::core::MountedAttribute<'a> {
-                dioxus::core::Attribute::new(
-                    stringify!($name),
-                    _cx.listener(move |e: dioxus::core::Event<$data>| {
-                        _f(e).spawn(_cx);
-                    }),
-                    None,
-                    false,
-                ).into()
+            paste::paste! {
+                $( #[$attr] )*
+                #[inline]
+                pub fn $name<'a, E: crate::element_core::EventReturn<T>, T>(_cx: &'a dioxus::core::ScopeState, mut _f: impl FnMut(dioxus::core::Event<UiEvent<<$data as crate::element_core::DomEventData>::Data>>) -> E + 'a) -> dioxus::core::MountedAttribute<'a> {
+                    dioxus::core::Attribute::new(
+                        stringify!($name),
+                        _cx.listener(move |e: dioxus::core::Event<UiEvent<<$data as crate::element_core::DomEventData>::Data>>| {
+                            _f(e).spawn(_cx);
+                        }),
+                        None,
+                        false,
+                    ).into()
+                }
+
+                $( #[$attr] )*
+                #[inline]
+                pub fn [<$name _capture>]<'a, E: crate::element_core::EventReturn<T>, T>(_cx: &'a dioxus::core::ScopeState, mut _f: impl FnMut(dioxus::core::Event<UiEvent<<$data as crate::element_core::DomEventData>::Data>>) -> E + 'a) -> dioxus::core::MountedAttribute<'a> {
+                    dioxus::core::Attribute::new(
+                        concat!(stringify!($name), "_capture"),
+                        _cx.listener(move |e: dioxus::core::Event<UiEvent<<$data as crate::element_core::DomEventData>::Data>>| {
+                            _f(e).spawn(_cx);
+                        }),
+                        None,
+                        false,
+                    ).into()
+                }
+
+                $( #[$attr] )*
+                #[inline]
+                pub fn [<$name _passive>]<'a, E: crate::element_core::EventReturn<T>, T>(_cx: &'a dioxus::core::ScopeState, mut _f: impl FnMut(dioxus::core::Event<UiEvent<<$data as crate::element_core::DomEventData>::Data>>) -> E + 'a) -> dioxus::core::MountedAttribute<'a> {
+                    dioxus::core::Attribute::new(
+                        concat!(stringify!($name), "_passive"),
+                        _cx.listener(move |e: dioxus::core::Event<UiEvent<<$data as crate::element_core::DomEventData>::Data>>| {
+                            _f(e).spawn(_cx);
+                        }),
+                        None,
+                        false,
+                    ).into()
+                }
             }
         )*
     };
-}
\ No newline at end of file
+}
@@ -0,0 +1,180 @@
+use std::sync::{Arc, RwLock};
+
+use bevy::prelude::Resource;
+use bevy::utils::HashMap;
+
+use crate::dom_commands::DomAttributeValue;
+use crate::element_attrs::SetAttrValueContext;
+use crate::element_core::{
+    element_attr_count, AttrValue, ElementAttrUntyped, ElementType, ElementTypeBase,
+    ElementTypeUnTyped,
+};
+use crate::entity_extra_data::{AttrIndex, AttrSetBits};
+use crate::prelude::warn;
+use crate::smallbox::S1;
+use crate::{DioxusAttributeDescription, SmallBox};
+
+/// `HasIndex::INDEX` of attrs registered at runtime, their real index is assigned by `ElementRegistry`
+/// and looked up by `ElementAttr::attr_index`
+pub const CUSTOM_ATTR_INDEX: AttrIndex = AttrIndex::MAX;
+
+/// Attr registered on an existing element, placed after the attrs of its definition
+pub struct CustomAttr {
+    attr: &'static dyn ElementAttrUntyped,
+    index: AttrIndex,
+}
+
+impl ElementAttrUntyped for CustomAttr {
+    #[inline]
+    fn attr_name(&self) -> &'static str {
+        self.attr.attr_name()
+    }
+
+    #[inline]
+    fn namespace(&self) -> Option<&'static str> {
+        self.attr.namespace()
+    }
+
+    #[inline]
+    fn volatile(&self) -> bool {
+        self.attr.volatile()
+    }
+
+    #[inline]
+    fn attribute_description(&self) -> DioxusAttributeDescription {
+        self.attr.attribute_description()
+    }
+
+    #[inline]
+    fn index(&self) -> AttrIndex {
+        self.index
+    }
+
+    #[inline]
+    fn set_by_attr_value(&self, context: &mut SetAttrValueContext, value: DomAttributeValue) {
+        self.attr.set_by_attr_value(context, value);
+    }
+
+    #[inline]
+    fn set_dyn_value(&self, context: &mut SetAttrValueContext, value: SmallBox<dyn AttrValue, S1>) {
+        self.attr.set_dyn_value(context, value);
+    }
+
+    #[inline]
+    fn set_to_default_value(&self, context: &mut SetAttrValueContext) {
+        self.attr.set_to_default_value(context);
+    }
+
+    #[inline]
+    fn parse_dyn_value(&self, value: DomAttributeValue) -> Option<SmallBox<dyn AttrValue, S1>> {
+        self.attr.parse_dyn_value(value)
+    }
+
+    fn set_dyn_value_in_class(
+        &self,
+        context: &mut SetAttrValueContext,
+        value: SmallBox<dyn AttrValue, S1>,
+    ) {
+        let entity_extra_data = context.entity_extra_data();
+        if !entity_extra_data.is_set_attr(self.index) {
+            entity_extra_data.set_class_attr(self.index, true);
+            self.attr.set_dyn_value(context, value);
+        }
+    }
+}
+
+#[derive(Default)]
+struct ElementRegistryInner {
+    elements: HashMap<&'static str, &'static dyn ElementTypeUnTyped>,
+    attrs: HashMap<&'static str, Vec<&'static CustomAttr>>,
+}
+
+/// Element types and attrs registered by applications, looked up by name after the built-in ones.
+/// Each `App` has its own, cloning shares the same registry, like `AppTypeRegistry`.
+#[derive(Resource, Clone, Default)]
+pub struct ElementRegistry {
+    inner: Arc<RwLock<ElementRegistryInner>>,
+}
+
+impl ElementRegistry {
+    pub fn register<T: ElementTypeBase + ElementType + Default>(&self) {
+        let attr_count = element_attr_count(T::ATTRS);
+        assert!(
+            attr_count <= AttrSetBits::CAPACITY,
+            "element {} has {} attrs, more than the {} supported",
+            T::TAG_NAME,
+            attr_count,
+            AttrSetBits::CAPACITY
+        );
+        let element: &'static dyn ElementTypeUnTyped = Box::leak(Box::new(T::default()));
+        self.inner
+            .write()
+            .unwrap()
+            .elements
+            .insert(T::TAG_NAME, element);
+    }
+
+    /// Adds an attr to an existing element, `attr` usually is a unit struct implementing `ElementAttr`
+    pub fn register_attr<E: ElementTypeBase>(&self, attr: impl ElementAttrUntyped + 'static) {
+        let mut inner = self.inner.write().unwrap();
+        let attrs = inner.attrs.entry(E::TAG_NAME).or_default();
+        let attr_name = attr.attr_name();
+        if E::attr(attr_name).is_some() || attrs.iter().any(|n| n.attr_name() == attr_name) {
+            warn!("attr {} of element {} is already registered", attr_name, E::TAG_NAME);
+            return;
+        }
+        let index = element_attr_count(E::ATTRS) + attrs.len();
+        assert!(
+            index < AttrSetBits::CAPACITY,
+            "element {} has more than the {} supported attrs",
+            E::TAG_NAME,
+            AttrSetBits::CAPACITY
+        );
+        attrs.push(Box::leak(Box::new(CustomAttr {
+            attr: Box::leak(Box::new(attr)),
+            index: index as AttrIndex,
+        })));
+    }
+
+    pub fn get(&self, tag_name: &str) -> Option<&'static dyn ElementTypeUnTyped> {
+        self.inner.read().unwrap().elements.get(tag_name).copied()
+    }
+
+    pub fn tag_names(&self) -> Vec<&'static str> {
+        self.inner.read().unwrap().elements.keys().copied().collect()
+    }
+
+    pub fn custom_attr(
+        &self,
+        tag_name: &str,
+        attr_name: &str,
+    ) -> Option<&'static dyn ElementAttrUntyped> {
+        let inner = self.inner.read().unwrap();
+        let attr = inner
+            .attrs
+            .get(tag_name)?
+            .iter()
+            .find(|n| n.attr_name() == attr_name)?;
+        Some(*attr)
+    }
+
+    pub fn custom_attr_index(&self, tag_name: &str, attr_name: &str) -> Option<AttrIndex> {
+        let inner = self.inner.read().unwrap();
+        let attr = inner
+            .attrs
+            .get(tag_name)?
+            .iter()
+            .find(|n| n.attr_name() == attr_name)?;
+        Some(attr.index)
+    }
+
+    pub fn custom_attr_by_index(
+        &self,
+        tag_name: &str,
+        index: AttrIndex,
+    ) -> Option<&'static dyn ElementAttrUntyped> {
+        let inner = self.inner.read().unwrap();
+        let attr = inner.attrs.get(tag_name)?.iter().find(|n| n.index == index)?;
+        Some(*attr)
+    }
+}
@@ -5,7 +5,7 @@ use bevy::ecs::world::EntityMut;
 use bevy::prelude::World;
 use bevy::reflect::{Reflect, ReflectFromPtr};
 
-use crate::{ElementCompositeAttrUntyped, ReflectExtension};
+use crate::{ElementCompositeAttrUntyped, ElementRegistry, ReflectExtension};
 use crate::element_core::ElementAttrUntyped;
 use crate::entity_extra_data::AttrIndex;
 use crate::prelude::{AppTypeRegistry, Entity, warn};
@@ -16,18 +16,27 @@ pub trait ElementTypeUnTyped: Reflect {
 
     fn attrs(&self) -> &'static [&'static [&'static dyn ElementAttrUntyped]];
 
-    fn attr(&self, attr_name: &str) -> Option<&'static dyn ElementAttrUntyped>;
+    /// Attr of the definition, then the ones registered on the element in `registry`
+    fn attr(
+        &self,
+        registry: &ElementRegistry,
+        attr_name: &str,
+    ) -> Option<&'static dyn ElementAttrUntyped>;
     fn composite_attrs(&self) -> &'static [&'static [&'static dyn ElementCompositeAttrUntyped]];
     fn composite_attr(&self, attr_name: &str) -> Option<&'static dyn ElementCompositeAttrUntyped>;
-    fn attr_by_index(&self, index: AttrIndex) -> &'static dyn ElementAttrUntyped {
-        let mut index = index as usize;
+    fn attr_by_index(
+        &self,
+        registry: &ElementRegistry,
+        index: AttrIndex,
+    ) -> Option<&'static dyn ElementAttrUntyped> {
+        let mut local_index = index as usize;
         for attrs in self.attrs() {
-            if index < attrs.len() {
-                return attrs[index];
+            if local_index < attrs.len() {
+                return Some(attrs[local_index]);
             }
-            index -= attrs.len();
+            local_index -= attrs.len();
         }
-        unreachable!();
+        registry.custom_attr_by_index(self.tag_name(), index)
     }
     fn spawn<'w>(&self, world: &'w mut World) -> EntityMut<'w>;
     fn try_insert_no_reflect_components(
@@ -57,8 +66,12 @@ impl<T: ElementTypeBase + ElementType> ElementTypeUnTyped for T {
     }
 
     #[inline]
-    fn attr(&self, attr_name: &str) -> Option<&'static dyn ElementAttrUntyped> {
-        T::attr(attr_name)
+    fn attr(
+        &self,
+        registry: &ElementRegistry,
+        attr_name: &str,
+    ) -> Option<&'static dyn ElementAttrUntyped> {
+        T::attr(attr_name).or_else(|| registry.custom_attr(T::TAG_NAME, attr_name))
     }
 
     #[inline]
@@ -95,6 +108,17 @@ impl<T: ElementTypeBase + ElementType> ElementTypeUnTyped for T {
     }
 }
 
+/// Total number of attrs of an element, usable in const context
+pub const fn element_attr_count(attrs: &[&[&dyn ElementAttrUntyped]]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < attrs.len() {
+        count += attrs[i].len();
+        i += 1;
+    }
+    count
+}
+
 pub trait ElementTypeBase: Reflect {
     const TAG_NAME: &'static str;
     const NAME_SPACE: Option<&'static str> = None;
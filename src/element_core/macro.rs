@@ -92,21 +92,73 @@ macro_rules! define_elements {
                    .register_type::<$crate::elements::$name>()
                 )*
          }
+
+         fn register_dioxus_element<T>(&mut self) -> &mut Self
+         where
+            T: $crate::ElementTypeBase + $crate::ElementType + Default + bevy::reflect::GetTypeRegistration,
+         {
+            let registry = self
+               .world
+               .get_resource_or_insert_with($crate::ElementRegistry::default)
+               .clone();
+            if try_get_element_type(&registry, T::TAG_NAME).is_some() {
+               bevy::log::warn!("element {} is already registered", T::TAG_NAME);
+               return self;
+            }
+            registry.register::<T>();
+            self.register_type::<T>()
+         }
+
+         fn register_dioxus_attr<E: $crate::ElementTypeBase>(
+            &mut self,
+            attr: impl $crate::ElementAttrUntyped + 'static,
+         ) -> &mut Self {
+            self.world
+               .get_resource_or_insert_with($crate::ElementRegistry::default)
+               .register_attr::<E>(attr);
+            self
+         }
       }
 
-     pub fn try_get_element_type(name: &str) -> Option<&'static dyn ElementTypeUnTyped> {
+     /// Built-in element types by tag name, then the ones registered in `registry`
+     pub fn try_get_element_type(
+         registry: &$crate::ElementRegistry,
+         name: &str,
+     ) -> Option<&'static dyn ElementTypeUnTyped> {
          match name {
             $(
                stringify!($name) => Some(&$crate::elements::$name),
                 )*
-                _ => None,
+                _ => registry.get(name),
             }
       }
 
-      pub fn get_element_type(name: &str) -> &'static dyn ElementTypeUnTyped {
-         try_get_element_type(name).unwrap_or_else(|| panic!("No Found ElementType by {:#?}", name))
+      pub fn get_element_type(
+         registry: &$crate::ElementRegistry,
+         name: &str,
+      ) -> &'static dyn ElementTypeUnTyped {
+         try_get_element_type(registry, name)
+            .unwrap_or_else(|| panic!("No Found ElementType by {:#?}", name))
       }
 
+      /// Attr count of the element with the most attrs, sizes `AttrSetBits`
+      pub const MAX_ELEMENT_ATTR_COUNT: usize = {
+         let counts = [
+            $(
+               $crate::element_attr_count(<$crate::elements::$name as $crate::ElementTypeBase>::ATTRS),
+            )*
+         ];
+         let mut max = 0;
+         let mut i = 0;
+         while i < counts.len() {
+            if counts[i] > max {
+               max = counts[i];
+            }
+            i += 1;
+         }
+         max
+      };
+
       $(
         $crate::define_element!(
             $(#[$m_attr])*
@@ -137,7 +189,7 @@ macro_rules! define_element {
         pub struct $name;
 
         paste::paste!{
-            $crate::attrs_trait_define!([<$name:camel Attrs>];<$name as $crate::CommonAttrs>::ATTRS.len() as u8;
+            $crate::attrs_trait_define!([<$name:camel Attrs>];<$name as $crate::CommonAttrs>::ATTRS.len() as $crate::AttrIndex;
                 $($attr),*
             );
 
@@ -2,6 +2,7 @@ pub use attr_value::*;
 pub use element_attr::*;
 pub use element_composite_attr::*;
 pub use element_event::*;
+pub use element_registry::*;
 pub use element_type::*;
 pub use r#macro::*;
 
@@ -11,11 +12,23 @@ mod attr_value;
 mod element_attr;
 mod element_composite_attr;
 mod element_event;
+mod element_registry;
 mod element_type;
 mod r#macro;
 
 pub trait BevyDioxusAppExt {
     fn register_elements_type(&mut self) -> &mut Self;
+
+    /// Makes an element defined outside of this crate usable in `rsx!` by its tag name
+    fn register_dioxus_element<T>(&mut self) -> &mut Self
+    where
+        T: ElementTypeBase + ElementType + Default + bevy::reflect::GetTypeRegistration;
+
+    /// Adds an attr to an existing element, e.g. a game specific `health` on `view`
+    fn register_dioxus_attr<E: ElementTypeBase>(
+        &mut self,
+        attr: impl ElementAttrUntyped + 'static,
+    ) -> &mut Self;
 }
 
 pub type DioxusAttributeDescription = (&'static str, Option<&'static str>, bool);
@@ -2,14 +2,26 @@
 #![allow(non_camel_case_types)]
 
 use std::any::TypeId;
+use std::collections::VecDeque;
 
+use bevy::asset::HandleId;
 use bevy::ecs::component::ComponentInfo;
 use bevy::ecs::world::EntityMut;
 use bevy::reflect::Reflect;
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{IoTaskPool, Task};
+use bevy::text::BreakLineOn;
+use bevy::utils::{HashMap, HashSet};
 use bevy_cosmic_edit::*;
+use cosmic_text::{Align, FamilyOwned, Wrap};
 
+use bevy::ui::Node;
+use bevy::window::{PrimaryWindow, Window};
 use input_attrs::*;
+use crate::element_core::HasIndex;
+use crate::entity_extra_data::{AttrIndex, EntitiesExtraData};
 use crate::input;
+use crate::ui_events::{TextChangeEvent, TextInputEvent, TextSubmitEvent};
 
 use crate::{
     ElementType,
@@ -27,10 +39,274 @@ pub fn bevy_color_to_cosmic(color: Color) -> CosmicColor {
     )
 }
 
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq)]
+#[reflect(Component, Default)]
+pub struct InputState {
+    pub committed_value: String,
+    /// incremented by every local edit, sent with [`TextInputEvent`]
+    pub revision: u64,
+    /// values of local edits not yet echoed back by the `value` attr, oldest first
+    #[reflect(ignore)]
+    pub pending_values: VecDeque<(u64, String)>,
+    /// revision of the edit the next `value` answers, set by the `value_revision` attr
+    pub value_revision: u64,
+}
+
+/// Local edits kept for matching late controlled values, older ones are dropped
+pub const MAX_PENDING_INPUT_VALUES: usize = 32;
+
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component, Default)]
+pub struct InputOptions {
+    pub multiline: bool,
+    /// visible line count of a multiline input, sets its height, 0 keeps the height
+    pub rows: u32,
+    /// max line count of a multiline input, 0 means unlimited
+    pub max_lines: u32,
+    pub wrap: bool,
+    pub linebreak: BreakLineOn,
+    pub text_align: TextAlignment,
+}
+
+impl Default for InputOptions {
+    fn default() -> Self {
+        Self {
+            multiline: false,
+            rows: 0,
+            max_lines: 0,
+            wrap: true,
+            linebreak: BreakLineOn::WordBoundary,
+            text_align: TextAlignment::Left,
+        }
+    }
+}
+
+impl InputOptions {
+    pub fn max_lines(&self) -> CosmicMaxLines {
+        CosmicMaxLines(if self.multiline { self.max_lines as usize } else { 1 })
+    }
+
+    /// content height showing `rows` lines, `None` for single line inputs or without `rows`
+    pub fn rows_height(&self, metrics: &CosmicMetrics) -> Option<f32> {
+        (self.multiline && self.rows > 0).then(|| self.rows as f32 * metrics.line_height)
+    }
+
+    pub fn mode(&self) -> CosmicMode {
+        if self.multiline && self.wrap {
+            CosmicMode::Wrap
+        } else {
+            CosmicMode::InfiniteLine
+        }
+    }
+
+    pub fn cosmic_wrap(&self) -> Wrap {
+        match self.linebreak {
+            BreakLineOn::WordBoundary => Wrap::Word,
+            BreakLineOn::AnyCharacter => Wrap::Glyph,
+        }
+    }
+
+    pub fn cosmic_align(&self) -> Option<Align> {
+        match self.text_align {
+            TextAlignment::Left => None,
+            TextAlignment::Center => Some(Align::Center),
+            TextAlignment::Right => Some(Align::Right),
+        }
+    }
+}
+
+#[derive(Component, Reflect, Default, Debug, Clone, PartialEq)]
+#[reflect(Component, Default)]
+pub struct InputFont(pub Handle<Font>);
+
+/// Cosmic-text family names of the bevy fonts loaded into [`CosmicFontSystem`]
+#[derive(Resource, Default)]
+pub struct CosmicFontFamilies {
+    families: HashMap<HandleId, String>,
+    loading: HashMap<HandleId, Task<Option<Vec<u8>>>>,
+}
+
+impl CosmicFontFamilies {
+    pub fn get(&self, font: &Handle<Font>) -> Option<&str> {
+        self.families.get(&font.id()).map(|n| n.as_str())
+    }
+}
+
+fn load_font_family(font_system: &mut CosmicFontSystem, bytes: Vec<u8>) -> Option<String> {
+    let db = font_system.db_mut();
+    let loaded_ids: HashSet<_> = db.faces().map(|face| face.id).collect();
+    db.load_font_data(bytes);
+    db.faces()
+        .find(|face| !loaded_ids.contains(&face.id))
+        .and_then(|face| face.families.first())
+        .map(|(family, _)| family.clone())
+}
+
+// Bevy's `Font` only keeps the parsed glyph data, so the font file is read again
+// through the asset io and registered in the cosmic font system.
+pub fn update_input_fonts(
+    asset_server: Res<AssetServer>,
+    mut font_system: ResMut<CosmicFontSystem>,
+    mut font_families: ResMut<CosmicFontFamilies>,
+    mut inputs: Query<(Ref<InputFont>, &mut CosmicAttrs)>,
+) {
+    let CosmicFontFamilies { families, loading } = &mut *font_families;
+    let mut any_loaded = false;
+    loading.retain(|id, task| {
+        let Some(bytes) = future::block_on(future::poll_once(task)) else {
+            return true;
+        };
+        if let Some(family) = bytes.and_then(|bytes| load_font_family(&mut font_system, bytes)) {
+            families.insert(*id, family);
+            any_loaded = true;
+        } else {
+            warn!("load font {:?} into cosmic font system failed!", id);
+        }
+        false
+    });
+
+    for (font, mut attrs) in inputs.iter_mut() {
+        if !font.is_changed() && !any_loaded {
+            continue;
+        }
+        let id = font.0.id();
+        if let Some(family) = families.get(&id) {
+            let family = FamilyOwned::Name(family.clone());
+            if attrs.0.family_owned != family {
+                attrs.0.family_owned = family;
+            }
+            continue;
+        }
+        if !font.is_changed() || loading.contains_key(&id) {
+            continue;
+        }
+        let Some(path) = asset_server.get_handle_path(&font.0) else {
+            warn!("font {:?} has no asset path, can't be used by input!", id);
+            continue;
+        };
+        let asset_server = asset_server.clone();
+        let path = path.path().to_path_buf();
+        loading.insert(
+            id,
+            IoTaskPool::get().spawn(async move {
+                asset_server
+                    .asset_io()
+                    .load_path(&path)
+                    .await
+                    .map_err(|err| warn!("read font file {:?} failed: {}", path, err))
+                    .ok()
+            }),
+        );
+    }
+}
+
+pub fn update_input_text_layout(
+    mut font_system: ResMut<CosmicFontSystem>,
+    mut text_changed_events: EventReader<CosmicTextChanged>,
+    mut inputs: Query<(Entity, Ref<InputOptions>, Ref<CosmicText>, &mut CosmicEditor)>,
+) {
+    // new lines take the default align, the lines are only checked when options or text change
+    let text_changed = text_changed_events
+        .iter()
+        .map(|CosmicTextChanged((entity, _))| *entity)
+        .collect::<HashSet<_>>();
+    for (entity, options, text, editor) in inputs.iter_mut() {
+        if options.is_changed() || text.is_changed() || text_changed.contains(&entity) {
+            update_editor_layout(&mut font_system, &options, editor);
+        }
+    }
+}
+
+/// Only touches the editor when needed, it's redrawn whenever it is marked changed
+fn update_editor_layout(
+    font_system: &mut CosmicFontSystem,
+    options: &InputOptions,
+    mut editor: Mut<CosmicEditor>,
+) {
+    let wrap = options.cosmic_wrap();
+    let align = options.cosmic_align();
+    let buffer = editor.0.buffer();
+    let wrap_changed = options.mode() == CosmicMode::Wrap && buffer.wrap() != wrap;
+    let align_changed = buffer.lines.iter().any(|line| line.align() != align);
+    if !wrap_changed && !align_changed {
+        return;
+    }
+    let buffer = editor.0.buffer_mut();
+    if wrap_changed {
+        buffer.set_wrap(font_system, wrap);
+    }
+    if align_changed {
+        for line in buffer.lines.iter_mut() {
+            line.set_align(align);
+        }
+    }
+}
+
+/// Sets the height of multiline inputs from `rows`, paddings and borders, unless the `height`
+/// attr, a class or a stylesheet sets it
+pub fn update_input_rows_height(
+    entities_extra_data: Res<EntitiesExtraData>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    nodes: Query<&Node>,
+    mut inputs: Query<(Entity, &InputOptions, &CosmicMetrics, Option<&Parent>, &mut Style)>,
+) {
+    const HEIGHT: AttrIndex = <crate::all_attrs::height as HasIndex>::INDEX;
+    let viewport = windows
+        .get_single()
+        .map(|window| Vec2::new(window.width(), window.height()))
+        .unwrap_or_default();
+    for (entity, options, metrics, parent, mut style) in inputs.iter_mut() {
+        let Some(rows_height) = options.rows_height(metrics) else {
+            continue;
+        };
+        let height_is_set = entities_extra_data.get(&entity).is_some_and(|data| {
+            data.is_set_attr(HEIGHT)
+                || data.is_set_class_attr(HEIGHT)
+                || data.stylesheet_values.contains_key(&HEIGHT)
+        });
+        if height_is_set {
+            continue;
+        }
+        // like the layout, percentages of paddings and borders are of the parent width
+        let parent_width = parent
+            .and_then(|parent| nodes.get(parent.get()).ok())
+            .map_or(viewport.x, |node| node.size().x);
+        let resolve = |val: Val| match val {
+            Val::Px(n) => n,
+            Val::Percent(n) => parent_width * n / 100.0,
+            Val::Vw(n) => viewport.x * n / 100.0,
+            Val::Vh(n) => viewport.y * n / 100.0,
+            Val::VMin(n) => viewport.min_element() * n / 100.0,
+            Val::VMax(n) => viewport.max_element() * n / 100.0,
+            Val::Auto => 0.0,
+        };
+        let height = Val::Px(
+            rows_height
+                + resolve(style.padding.top)
+                + resolve(style.padding.bottom)
+                + resolve(style.border.top)
+                + resolve(style.border.bottom),
+        );
+        if style.height != height {
+            style.height = height;
+        }
+    }
+}
+
+fn update_input_options(entity_ref: &mut EntityMut, f: impl FnOnce(&mut InputOptions)) {
+    let Some(mut options) = entity_ref.get_mut::<InputOptions>() else {
+        warn!("no found InputOptions component!");
+        return;
+    };
+    f(&mut options);
+    let options = *options;
+    entity_ref.insert((options.max_lines(), options.mode()));
+}
+
 impl ElementType for input {
     fn spawn<'w>(&self, world: &'w mut World) -> EntityMut<'w> {
         let attrs = AttrsOwned::new(Attrs::new().color(bevy_color_to_cosmic(Color::BLACK)));
-        let _placeholder_attrs = AttrsOwned::new(
+        let placeholder_attrs = AttrsOwned::new(
             Attrs::new().color(bevy_color_to_cosmic(Color::hex("#e6e6e6").unwrap())),
         );
 
@@ -49,11 +325,11 @@ impl ElementType for input {
                 line_height: 18. * 1.,
                 scale_factor: 1.,
             },
-            max_lines: CosmicMaxLines(1),
+            max_lines: InputOptions::default().max_lines(),
             text_setter: CosmicText::OneStyle(String::from("")),
-            mode: CosmicMode::InfiniteLine,
+            mode: InputOptions::default().mode(),
             ..default()
-        }, ))
+        }, PlaceholderAttrs(placeholder_attrs), InputState::default(), InputOptions::default()))
     }
 
     fn try_insert_no_reflect_components(
@@ -99,6 +375,29 @@ impl ElementType for input {
                         .unwrap(),
                 );
             }
+            n if n == TypeId::of::<PasswordInput>() => {
+                let password = template_world.get::<PasswordInput>(template_entity).unwrap();
+                _entity_mut.insert(PasswordInput(password.0));
+            }
+            n if n == TypeId::of::<ReadOnly>() => {
+                _entity_mut.insert(ReadOnly);
+            }
+            n if n == TypeId::of::<CosmicMaxChars>() => {
+                let max_chars = template_world.get::<CosmicMaxChars>(template_entity).unwrap();
+                _entity_mut.insert(CosmicMaxChars(max_chars.0));
+            }
+            n if n == TypeId::of::<CosmicMaxLines>() => {
+                let max_lines = template_world.get::<CosmicMaxLines>(template_entity).unwrap();
+                _entity_mut.insert(CosmicMaxLines(max_lines.0));
+            }
+            n if n == TypeId::of::<CosmicMode>() => {
+                _entity_mut.insert(
+                    template_world
+                        .get::<CosmicMode>(template_entity)
+                        .cloned()
+                        .unwrap(),
+                );
+            }
             _ => return false,
         }
         true
@@ -109,13 +408,9 @@ impl TextStyledElementType for input {
     fn set_font(
         &self,
         entity_ref: &mut EntityMut,
-        _v: <crate::element_attrs::font as ElementAttr>::Value,
+        v: <crate::element_attrs::font as ElementAttr>::Value,
     ) {
-        let Some(_attrs) = entity_ref.get_mut::<CosmicAttrs>() else {
-            return;
-        };
-        // todo: CosmicText font set
-        //        attrs.0.family_owned
+        entity_ref.insert(InputFont(v));
     }
 
     fn set_font_size(
@@ -143,10 +438,10 @@ impl TextStyledElementType for input {
 
     fn set_text_linebreak(
         &self,
-        _entity_ref: &mut EntityMut,
-        _v: <crate::element_attrs::text_linebreak as ElementAttr>::Value,
+        entity_ref: &mut EntityMut,
+        v: <crate::element_attrs::text_linebreak as ElementAttr>::Value,
     ) {
-        // todo: CosmicText text_linebreak
+        update_input_options(entity_ref, |options| options.linebreak = v);
     }
 
     fn set_text_align(
@@ -154,11 +449,13 @@ impl TextStyledElementType for input {
         entity_ref: &mut EntityMut,
         v: <crate::element_attrs::text_align as ElementAttr>::Value,
     ) {
+        update_input_options(entity_ref, |options| options.text_align = v);
         let Some(mut pos) = entity_ref.get_mut::<CosmicTextPosition>() else {
             return;
         };
         match v {
-            TextAlignment::Left => {
+            // right aligned lines are laid out by cosmic-text inside the left positioned buffer
+            TextAlignment::Left | TextAlignment::Right => {
                 if !matches!(*pos, CosmicTextPosition::Left { .. }) {
                     *pos = CosmicTextPosition::Left { padding: 0 }
                 }
@@ -166,13 +463,81 @@ impl TextStyledElementType for input {
             TextAlignment::Center => {
                 *pos = CosmicTextPosition::Center;
             }
-            TextAlignment::Right => {
-                //                if !matches!(*pos, CosmicTextPosition::Right { .. }) {
-                //                    *pos = CosmicTextPosition::Right { padding: 0 }
-                //                }
+        }
+    }
+}
+
+pub fn dispatch_input_text_events(
+    mut text_changed_events: EventReader<CosmicTextChanged>,
+    mut input_events: EventWriter<TextInputEvent>,
+    mut inputs: Query<&mut InputState>,
+) {
+    for CosmicTextChanged((entity, value)) in text_changed_events.iter() {
+        let Ok(mut state) = inputs.get_mut(*entity) else {
+            continue;
+        };
+        state.revision += 1;
+        let revision = state.revision;
+        if state.pending_values.len() == MAX_PENDING_INPUT_VALUES {
+            state.pending_values.pop_front();
+        }
+        state.pending_values.push_back((revision, value.clone()));
+        input_events.send(TextInputEvent {
+            target: *entity,
+            value: value.clone(),
+            revision,
+        });
+    }
+}
+
+fn commit_input_value(
+    entity: Entity,
+    state: &mut InputState,
+    value: &str,
+    change_events: &mut EventWriter<TextChangeEvent>,
+) {
+    if state.committed_value == value {
+        return;
+    }
+    state.committed_value = value.to_string();
+    change_events.send(TextChangeEvent {
+        target: entity,
+        value: value.to_string(),
+    });
+}
+
+pub fn dispatch_input_commit_events(
+    focus: Res<Focus>,
+    keys: Res<Input<KeyCode>>,
+    mut previous_focus: Local<Option<Entity>>,
+    mut inputs: Query<(&mut InputState, &CosmicEditor)>,
+    mut change_events: EventWriter<TextChangeEvent>,
+    mut submit_events: EventWriter<TextSubmitEvent>,
+) {
+    if *previous_focus != focus.0 {
+        if let Some(entity) = previous_focus.take() {
+            if let Ok((mut state, editor)) = inputs.get_mut(entity) {
+                commit_input_value(entity, &mut state, &editor.get_text(), &mut change_events);
             }
         }
+        *previous_focus = focus.0;
     }
+
+    if !keys.any_just_pressed([KeyCode::Return, KeyCode::NumpadEnter]) {
+        return;
+    }
+    let Some(entity) = focus.0 else {
+        return;
+    };
+    let Ok((mut state, editor)) = inputs.get_mut(entity) else {
+        return;
+    };
+    let value = editor.get_text();
+    commit_input_value(entity, &mut state, &value, &mut change_events);
+    submit_events.send(TextSubmitEvent {
+        target: entity,
+        value,
+    });
 }
 
 pub mod input_attrs {
@@ -186,11 +551,186 @@ pub mod input_attrs {
         const TAG_NAME: &'static str = stringify!(value);
 
         fn set_value(&self, context: &mut SetAttrValueContext, p_value: impl Into<Self::Value>) {
+            let value = p_value.into();
+            if let Some(mut state) = context.entity_ref.get_mut::<InputState>() {
+                // a controlled value echoing a local edit, possibly frames late, never replaces
+                // newer text, edits up to it are acknowledged. Without `value_revision` only the
+                // newest edit is an echo, an older value rejects the edits after it.
+                let revision = match state.value_revision {
+                    0 => state.pending_values.back().map(|(revision, _)| *revision),
+                    revision => Some(revision),
+                };
+                let echoed = state
+                    .pending_values
+                    .iter()
+                    .position(|(n_revision, n)| Some(*n_revision) == revision && *n == value);
+                if let Some(index) = echoed {
+                    state.pending_values.drain(..=index);
+                    return;
+                }
+                state.pending_values.clear();
+            }
+            // keep the cursor when the value is what the editor already shows
+            if let Some(editor) = context.entity_ref.get::<CosmicEditor>() {
+                if editor.get_text() == value {
+                    return;
+                }
+            }
+            if let Some(mut state) = context.entity_ref.get_mut::<InputState>() {
+                state.committed_value = value.clone();
+            }
             if let Some(mut t) = context.entity_ref.get_mut::<CosmicText>() {
-                *t = CosmicText::OneStyle(p_value.into());
+                *t = CosmicText::OneStyle(value);
             } else {
                 warn!("no found CosmicText component!");
             }
         }
     }
+
+    /// `revision` of the [`TextInputEvent`] the controlled `value` answers, set it before
+    /// `value`
+    pub struct value_revision;
+
+    impl ElementAttr for value_revision {
+        type Value = u64;
+
+        const TAG_NAME: &'static str = stringify!(value_revision);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            if let Some(mut state) = context.entity_ref.get_mut::<InputState>() {
+                state.value_revision = value.into();
+            }
+        }
+    }
+
+    pub struct placeholder;
+
+    impl ElementAttr for placeholder {
+        type Value = String;
+
+        const TAG_NAME: &'static str = stringify!(placeholder);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            context
+                .entity_ref
+                .insert(PlaceholderText(CosmicText::OneStyle(value.into())));
+        }
+    }
+
+    pub struct placeholder_color;
+
+    impl ElementAttr for placeholder_color {
+        type Value = Color;
+
+        const TAG_NAME: &'static str = stringify!(placeholder_color);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            let color = bevy_color_to_cosmic(value.into());
+            if let Some(mut attrs) = context.entity_ref.get_mut::<PlaceholderAttrs>() {
+                attrs.0.color_opt = Some(color);
+            } else {
+                context
+                    .entity_ref
+                    .insert(PlaceholderAttrs(AttrsOwned::new(Attrs::new().color(color))));
+            }
+        }
+    }
+
+    pub struct password;
+
+    impl ElementAttr for password {
+        type Value = bool;
+
+        const TAG_NAME: &'static str = stringify!(password);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            if value.into() {
+                context.entity_ref.insert(PasswordInput::default());
+            } else {
+                context.entity_ref.remove::<PasswordInput>();
+            }
+        }
+    }
+
+    pub struct readonly;
+
+    impl ElementAttr for readonly {
+        type Value = bool;
+
+        const TAG_NAME: &'static str = stringify!(readonly);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            if value.into() {
+                context.entity_ref.insert(ReadOnly);
+            } else {
+                context.entity_ref.remove::<ReadOnly>();
+            }
+        }
+    }
+
+    pub struct max_length;
+
+    impl ElementAttr for max_length {
+        type Value = u32;
+
+        const TAG_NAME: &'static str = stringify!(max_length);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            context
+                .entity_ref
+                .insert(CosmicMaxChars(value.into() as usize));
+        }
+    }
+
+    pub struct multiline;
+
+    impl ElementAttr for multiline {
+        type Value = bool;
+
+        const TAG_NAME: &'static str = stringify!(multiline);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            let value = value.into();
+            update_input_options(context.entity_ref, |options| options.multiline = value);
+        }
+    }
+
+    pub struct rows;
+
+    impl ElementAttr for rows {
+        type Value = u32;
+
+        const TAG_NAME: &'static str = stringify!(rows);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            let value = value.into();
+            update_input_options(context.entity_ref, |options| options.rows = value);
+        }
+    }
+
+    pub struct max_lines;
+
+    impl ElementAttr for max_lines {
+        type Value = u32;
+
+        const TAG_NAME: &'static str = stringify!(max_lines);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            let value = value.into();
+            update_input_options(context.entity_ref, |options| options.max_lines = value);
+        }
+    }
+
+    pub struct wrap;
+
+    impl ElementAttr for wrap {
+        type Value = bool;
+
+        const TAG_NAME: &'static str = stringify!(wrap);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            let value = value.into();
+            update_input_options(context.entity_ref, |options| options.wrap = value);
+        }
+    }
 }
@@ -0,0 +1,137 @@
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+
+use bevy::ecs::world::EntityMut;
+use bevy::prelude::*;
+
+use scroll_view_attrs::*;
+
+use crate::ecs_fns::StyleEntityExt;
+use crate::ui_events::ScrollEvent;
+use crate::{scroll_view, ElementAttr, ElementType, SetAttrValueContext};
+
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component, Default)]
+pub struct ScrollView {
+    pub offset: Vec2,
+    pub view_size: Vec2,
+    pub content_size: Vec2,
+    last_offset: Vec2,
+}
+
+impl ScrollView {
+    pub fn max_offset(&self, style: &Style) -> Vec2 {
+        let max = (self.content_size - self.view_size).max(Vec2::ZERO);
+        Vec2::new(
+            if style.overflow.x == OverflowAxis::Clip {
+                max.x
+            } else {
+                0.0
+            },
+            if style.overflow.y == OverflowAxis::Clip {
+                max.y
+            } else {
+                0.0
+            },
+        )
+    }
+
+    pub fn clamp_offset(&self, offset: Vec2, style: &Style) -> Vec2 {
+        offset.clamp(Vec2::ZERO, self.max_offset(style))
+    }
+}
+
+impl ElementType for scroll_view {
+    fn spawn<'w>(&self, world: &'w mut World) -> EntityMut<'w> {
+        world.spawn((
+            NodeBundle {
+                style: Style {
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                ..default()
+            },
+            ScrollView::default(),
+        ))
+    }
+}
+
+// Runs after `UiSystem::Layout`, which rewrites every node translation each frame,
+// so the offset is applied on top of freshly computed layout positions.
+pub fn update_scroll_view_layout(
+    mut scroll_views: Query<(Entity, &mut ScrollView, &Node, &Style, Option<&Children>)>,
+    mut nodes: Query<(&Node, &mut Transform)>,
+    mut scroll_events: EventWriter<ScrollEvent>,
+) {
+    for (entity, mut scroll_view, node, style, children) in scroll_views.iter_mut() {
+        let view_size = node.size();
+        let mut content_size = Vec2::ZERO;
+        for child in children.into_iter().flatten() {
+            let Ok((child_node, transform)) = nodes.get(*child) else {
+                continue;
+            };
+            content_size = content_size
+                .max(transform.translation.truncate() + (view_size + child_node.size()) / 2.0);
+        }
+        if scroll_view.view_size != view_size || scroll_view.content_size != content_size {
+            scroll_view.view_size = view_size;
+            scroll_view.content_size = content_size;
+        }
+
+        let offset = scroll_view.clamp_offset(scroll_view.offset, style);
+        if scroll_view.offset != offset {
+            scroll_view.offset = offset;
+        }
+        if scroll_view.last_offset != offset {
+            scroll_view.last_offset = offset;
+            scroll_events.send(ScrollEvent {
+                target: entity,
+                scroll_left: offset.x,
+                scroll_top: offset.y,
+            });
+        }
+        if offset == Vec2::ZERO {
+            continue;
+        }
+        for child in children.into_iter().flatten() {
+            let Ok((_, mut transform)) = nodes.get_mut(*child) else {
+                continue;
+            };
+            transform.translation -= offset.extend(0.0);
+        }
+    }
+}
+
+pub mod scroll_view_attrs {
+    use super::*;
+
+    pub struct scroll_top;
+
+    impl ElementAttr for scroll_top {
+        type Value = f32;
+
+        const TAG_NAME: &'static str = stringify!(scroll_top);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            let value = value.into();
+            context.entity_ref.try_set::<ScrollView>(|scroll_view| {
+                scroll_view.offset.y = value;
+            });
+        }
+    }
+
+    pub struct scroll_left;
+
+    impl ElementAttr for scroll_left {
+        type Value = f32;
+
+        const TAG_NAME: &'static str = stringify!(scroll_left);
+
+        fn set_value(&self, context: &mut SetAttrValueContext, value: impl Into<Self::Value>) {
+            let value = value.into();
+            context.entity_ref.try_set::<ScrollView>(|scroll_view| {
+                scroll_view.offset.x = value;
+            });
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::reflect::Reflect;
 
 pub use element_input::*;
+pub use element_scroll_view::*;
 pub use element_text::*;
 pub use element_view::*;
 
@@ -10,6 +11,7 @@ use crate::{
 };
 
 mod element_input;
+mod element_scroll_view;
 mod element_text;
 mod element_view;
 
@@ -32,7 +34,25 @@ define_elements!(
     #[reflect(TextStyledElementType)]
     input {
         [attrs]
+        value_revision
         text_value
+        placeholder
+        placeholder_color
+        password
+        readonly
+        max_length
+        multiline
+        rows
+        max_lines
+        wrap
+        [composite_attrs]
+    }
+
+    #[derive(Reflect, Debug, Clone, Copy)]
+    scroll_view {
+        [attrs]
+        scroll_top
+        scroll_left
         [composite_attrs]
     }
 );
\ No newline at end of file
@@ -1,41 +1,269 @@
-use std::ops::{Deref, DerefMut};
+use std::ops::{BitAnd, BitOr, BitOrAssign, Deref, DerefMut, Not};
 
-use bevy::prelude::{Entity, Resource};
-use bevy::utils::{default, HashMap};
+use bevy::prelude::{Component, Entity, Reflect, ReflectComponent, ReflectDefault, Resource};
+use bevy::utils::{default, HashMap, HashSet};
+use bevy_mod_picking::prelude::Pickable;
 use smallvec::SmallVec;
 
 use crate::element_core::AttrValue;
+use crate::elements::MAX_ELEMENT_ATTR_COUNT;
 use crate::SmallBox;
 use crate::smallbox::S1;
 use crate::tailwind::TailwindClassItem;
 
-pub type AttrSetBits = u64;
-pub type AttrIndex = u8;
+pub type AttrIndex = u16;
+
+/// Extra bits left for elements registered by applications
+const RESERVED_ATTR_COUNT: usize = 64;
+const ATTR_SET_WORDS: usize = (MAX_ELEMENT_ATTR_COUNT + RESERVED_ATTR_COUNT + 63) / 64;
 
 pub fn get_all_prop_indecs() -> impl Iterator<Item=AttrIndex> {
-    let bit_count = (std::mem::size_of::<AttrSetBits>() * 8) as AttrIndex;
-    0..bit_count
+    0..AttrSetBits::CAPACITY as AttrIndex
+}
+
+/// One bit per attr index, sized from the element with the most attrs
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct AttrSetBits([u64; ATTR_SET_WORDS]);
+
+impl AttrSetBits {
+    pub const EMPTY: Self = Self([0; ATTR_SET_WORDS]);
+    pub const CAPACITY: usize = ATTR_SET_WORDS * 64;
+
+    /// Indices past [`Self::CAPACITY`] are never contained
+    #[inline]
+    pub fn contains(&self, index: AttrIndex) -> bool {
+        let index = index as usize;
+        self.0
+            .get(index / 64)
+            .is_some_and(|word| (word >> (index % 64)) & 1 == 1)
+    }
+
+    /// Indices past [`Self::CAPACITY`] are ignored
+    #[inline]
+    pub fn insert(&mut self, index: AttrIndex) {
+        let index = index as usize;
+        if let Some(word) = self.0.get_mut(index / 64) {
+            *word |= 1 << (index % 64);
+        }
+    }
+
+    #[inline]
+    pub fn remove(&mut self, index: AttrIndex) {
+        let index = index as usize;
+        if let Some(word) = self.0.get_mut(index / 64) {
+            *word &= !(1 << (index % 64));
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|n| *n == 0)
+    }
+
+    /// Iterates the set indices in ascending order, skipping empty words
+    pub fn iter(self) -> impl Iterator<Item=AttrIndex> {
+        self.0
+            .into_iter()
+            .enumerate()
+            .flat_map(|(word_index, mut word)| {
+                std::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some((word_index * 64 + bit) as AttrIndex)
+                })
+            })
+    }
+}
+
+impl BitAnd for AttrSetBits {
+    type Output = Self;
+
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        for (word, rhs) in self.0.iter_mut().zip(rhs.0) {
+            *word &= rhs;
+        }
+        self
+    }
+}
+
+impl BitOr for AttrSetBits {
+    type Output = Self;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+impl BitOrAssign for AttrSetBits {
+    fn bitor_assign(&mut self, rhs: Self) {
+        for (word, rhs) in self.0.iter_mut().zip(rhs.0) {
+            *word |= rhs;
+        }
+    }
+}
+
+impl Not for AttrSetBits {
+    type Output = Self;
+
+    fn not(mut self) -> Self::Output {
+        for word in self.0.iter_mut() {
+            *word = !*word;
+        }
+        self
+    }
+}
+
+/// States matched by class variants like `hover:` or `first:`.
+/// A variant with a higher bit takes precedence over lower ones.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct ElementStates(u32);
+
+impl ElementStates {
+    pub const NONE: Self = Self(0);
+    pub const FIRST: Self = Self(1 << 0);
+    pub const LAST: Self = Self(1 << 1);
+    pub const ODD: Self = Self(1 << 2);
+    pub const EVEN: Self = Self(1 << 3);
+    pub const GROUP_HOVER: Self = Self(1 << 4);
+    pub const CHECKED: Self = Self(1 << 5);
+    pub const FOCUS: Self = Self(1 << 6);
+    pub const HOVER: Self = Self(1 << 7);
+    pub const ACTIVE: Self = Self(1 << 8);
+    pub const DISABLED: Self = Self(1 << 9);
+    pub const SM: Self = Self(1 << 10);
+    pub const MD: Self = Self(1 << 11);
+    pub const LG: Self = Self(1 << 12);
+    pub const XL: Self = Self(1 << 13);
+    pub const XXL: Self = Self(1 << 14);
+    pub const DARK: Self = Self(1 << 15);
+
+    /// Bits left for the named color schemes of the theme, e.g. `orc:`
+    pub const MAX_COLOR_SCHEMES: usize = 16;
+
+    /// Responsive variants, matched when the viewport is at least as wide as the theme breakpoint
+    pub const BREAKPOINTS: [(&'static str, Self); 5] = [
+        ("sm", Self::SM),
+        ("md", Self::MD),
+        ("lg", Self::LG),
+        ("xl", Self::XL),
+        ("2xl", Self::XXL),
+    ];
+
+    pub fn from_variant(variant: &str) -> Option<Self> {
+        Some(match variant {
+            "first" => Self::FIRST,
+            "last" => Self::LAST,
+            "odd" => Self::ODD,
+            "even" => Self::EVEN,
+            "group-hover" => Self::GROUP_HOVER,
+            "checked" => Self::CHECKED,
+            "focus" => Self::FOCUS,
+            "hover" => Self::HOVER,
+            "active" => Self::ACTIVE,
+            "disabled" => Self::DISABLED,
+            "dark" => Self::DARK,
+            variant => {
+                return Self::BREAKPOINTS
+                    .iter()
+                    .find(|(name, _)| *name == variant)
+                    .map(|(_, state)| *state);
+            }
+        })
+    }
+
+    /// State of the named color scheme at `index` of the theme
+    pub fn color_scheme(index: usize) -> Option<Self> {
+        (index < Self::MAX_COLOR_SCHEMES).then(|| Self(1 << (16 + index)))
+    }
+
+    #[inline]
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline]
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[inline]
+    pub fn set(&mut self, other: Self, value: bool) {
+        if value {
+            self.0 |= other.0;
+        } else {
+            self.0 &= !other.0;
+        }
+    }
 }
 
+impl BitOr for ElementStates {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ElementStates {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component, Default)]
+pub struct Disabled;
+
+/// `Pickable` of an element before it was disabled, restored once it is enabled again
+#[derive(Component, Debug, Clone)]
+pub struct PickableBeforeDisabled(pub Option<Pickable>);
+
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component, Default)]
+pub struct Checked;
+
+/// Marks an element with the `group` class, its hover state drives `group-hover:` of descendants
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component, Default)]
+pub struct UiGroup;
+
 #[derive(Clone)]
 pub struct EntityExtraData {
     pub schema_name: &'static str,
     pub attr_is_set: AttrSetBits,
     pub class_attr_is_set: AttrSetBits,
-    class_attr_set_count: u8,
+    /// last value of the `class` attr
+    pub classes: String,
     pub interaction_classes: SmallVec<[TailwindClassItem; 8]>,
-    pub normal_props_map: HashMap<u8, SmallBox<dyn AttrValue, S1>>,
+    pub states: ElementStates,
+    pub normal_props_map: HashMap<AttrIndex, SmallBox<dyn AttrValue, S1>>,
+    /// indices of the stylesheet rules matching the element, in the order they are applied
+    pub stylesheet_rules: SmallVec<[u32; 4]>,
+    /// attr values of the matching stylesheet rules, used where no attr or class sets them
+    pub stylesheet_values: HashMap<AttrIndex, SmallBox<dyn AttrValue, S1>>,
 }
 
 impl EntityExtraData {
     pub fn new(schema_name: &'static str) -> Self {
         Self {
             schema_name,
-            attr_is_set: 0,
-            class_attr_is_set: 0,
-            class_attr_set_count: 0,
+            attr_is_set: AttrSetBits::EMPTY,
+            class_attr_is_set: AttrSetBits::EMPTY,
+            classes: String::new(),
             interaction_classes: default(),
+            states: default(),
             normal_props_map: default(),
+            stylesheet_rules: default(),
+            stylesheet_values: default(),
         }
     }
     pub fn set_attr(&mut self, attr_index: AttrIndex, is_set: bool) {
@@ -43,45 +271,47 @@ impl EntityExtraData {
             return;
         }
         if is_set {
-            self.attr_is_set |= (1 << attr_index);
+            self.attr_is_set.insert(attr_index);
         } else {
-            self.attr_is_set &= !(1 << attr_index);
+            self.attr_is_set.remove(attr_index);
         }
     }
 
     pub fn is_set_attr(&self, attr_index: AttrIndex) -> bool {
-        (self.attr_is_set >> attr_index) & 1 == 1
+        self.attr_is_set.contains(attr_index)
     }
 
-    pub fn set_class_attr(&mut self, attr_index: AttrIndex, is_set: bool) {
-        if is_set == self.is_set_class_attr(attr_index) {
-            return;
+    /// Value of an attr that isn't set explicitly, the class value wins over the stylesheet value
+    pub fn fallback_value(&self, attr_index: AttrIndex) -> Option<SmallBox<dyn AttrValue, S1>> {
+        if self.is_set_attr(attr_index) {
+            None
+        } else if self.is_set_class_attr(attr_index) {
+            self.normal_props_map.get(&attr_index).cloned()
+        } else {
+            self.stylesheet_values.get(&attr_index).cloned()
         }
+    }
+
+    pub fn set_class_attr(&mut self, attr_index: AttrIndex, is_set: bool) {
         if is_set {
-            self.class_attr_is_set |= 1 << attr_index;
-            self.class_attr_set_count += 1;
+            self.class_attr_is_set.insert(attr_index);
         } else {
-            self.class_attr_is_set &= !(1 << attr_index);
-            self.class_attr_set_count -= 1;
+            self.class_attr_is_set.remove(attr_index);
         }
     }
 
     pub fn is_set_class_attr(&self, attr_index: AttrIndex) -> bool {
-        (self.class_attr_is_set >> attr_index) & 1 == 1
+        self.class_attr_is_set.contains(attr_index)
     }
 
     pub fn iter_set_class_attr_indices(&self) -> impl Iterator<Item=AttrIndex> + 'static {
-        let num = self.class_attr_is_set;
-        get_all_prop_indecs()
-            .filter(move |i| (num >> i) & 1 == 1)
-            .take(self.class_attr_set_count as usize)
+        self.class_attr_is_set.iter()
     }
     pub fn iter_class_attr_indices_exclude(
         &self,
         bits: AttrSetBits,
     ) -> impl Iterator<Item=AttrIndex> + 'static {
-        let num = self.class_attr_is_set & !bits;
-        get_all_prop_indecs().filter(move |i| (num >> i) & 1 == 1)
+        (self.class_attr_is_set & !bits).iter()
     }
 }
 
@@ -89,6 +319,8 @@ impl EntityExtraData {
 pub struct EntitiesExtraData {
     inner: HashMap<Entity, EntityExtraData>,
     pub empty_node_entities: Vec<Entity>,
+    /// elements whose classes changed, matched against the stylesheets again with their descendants
+    pub stylesheet_dirty: HashSet<Entity>,
 }
 
 impl Deref for EntitiesExtraData {
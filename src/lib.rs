@@ -2,6 +2,10 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+// lets the derive macros refer to `::bevy_dioxus` inside this crate too
+extern crate self as bevy_dioxus;
+
+pub use bevy_dioxus_macro::{attrs_extension, DioxusElement, ElementAttr};
 pub use dioxus_plugin::*;
 pub use ecs_fns::*;
 pub use element_attrs::*;
@@ -11,12 +15,14 @@ pub use entity_extra_data::AttrIndex;
 pub use smallbox::SmallBox;
 pub use text_styled_element::*;
 
+pub mod animation;
 pub mod apc;
 pub mod components;
 pub mod dioxus_ext;
 pub mod dioxus_plugin;
 pub mod dom_commands;
 pub mod dom_template;
+pub mod drag_drop;
 pub mod ecs_apc;
 pub mod ecs_fns;
 mod element_attrs;
@@ -24,14 +30,17 @@ mod element_core;
 pub mod elements;
 pub mod entity_extra_data;
 mod smallbox;
+pub mod stylesheet;
 pub mod tailwind;
 mod text_styled_element;
+pub mod ui_events;
 pub mod vdm_data;
 pub mod vdom_main;
 
 pub mod all_attrs {
     pub use crate::element_attrs::*;
     pub use crate::input_attrs::*;
+    pub use crate::scroll_view_attrs::*;
     pub use crate::text_attrs::*;
 }
 
@@ -47,15 +56,31 @@ pub mod prelude {
     pub use dioxus_elements::*;
 
     pub use crate::dioxus_ext::{use_cmd_sender, UseStateExt};
+    pub use crate::drag_drop::{DataTransfer, Draggable};
+    pub use crate::entity_extra_data::{Checked, Disabled, ElementStates, UiGroup};
     pub use crate::ecs_apc::world_call;
+    pub use crate::stylesheet::{StyleValue, UiStyleSheet, UiStyleSheetAppExt, UiStyleSheets};
+    pub use crate::tailwind::{
+        validate_classes, TailwindClassCache, TailwindDiagnostics, TailwindTheme,
+        TailwindThemeAppExt, UiColorScheme, UiViewportWidth, UnknownClassWarning,
+    };
     pub use crate::DioxusPlugin;
+    pub use crate::{attrs_extension, ui_components, DioxusElement, UiComponents};
+    pub use crate::animation::{
+        use_animation, AnimationEndEvent, Easing, KeyframeValues, Keyframes, TransitionProperties,
+        UiAnimation, UiKeyframes, UiTransition,
+    };
     pub use crate::{
-        CommonAttrs, CommonCompositeAttrs, ElementAttr, ElementAttrUntyped, ElementTypeBase,
-        ElementTypeUnTyped,
+        BevyDioxusAppExt, CommonAttrs, CommonCompositeAttrs, ElementAttr, ElementAttrUntyped,
+        ElementRegistry, ElementType, ElementTypeBase, ElementTypeUnTyped, EventPhase, UiEvent,
     };
     pub use crate::{
-        HasIndex, InputAttrs, InputCompositeAttrs, TextAttrs, TextCompositeAttrs, ViewAttrs,
-        ViewCompositeAttrs,
+        HasIndex, InputAttrs, InputCompositeAttrs, ScrollViewAttrs, ScrollViewCompositeAttrs,
+        TextAttrs, TextCompositeAttrs, ViewAttrs, ViewCompositeAttrs,
+    };
+    pub use crate::ui_events::{
+        DoubleClick, Modifiers, PointerEventData, ScrollEvent, TextChangeEvent, TextInputEvent,
+        TextSubmitEvent, UiPointerEvent, WheelEvent,
     };
     pub use dioxus_elements::extensions::*;
 
@@ -63,9 +88,9 @@ pub mod prelude {
     #[allow(non_upper_case_globals)]
     pub mod dioxus_elements {
 
-        pub use crate::{input, text, view};
+        pub use crate::{input, scroll_view, text, view};
 
-        pub(crate) mod extensions {
+        pub mod extensions {
             pub trait CommonAttrsExtension<'a>: crate::prelude::HasAttributes<'a> {}
             impl<'a, T: CommonAttrsExtension<'a>> crate::_CommonAttrsExtension<'a> for T {}
             impl<'a, T: CommonAttrsExtension<'a>> crate::_CommonCompositeAttrsExtension<'a> for T {}
@@ -99,17 +124,24 @@ pub mod prelude {
 
 
             pub use crate::InputAttrsExtension;
+            pub use crate::ScrollViewAttrsExtension;
             pub use crate::TextAttrsExtension;
             pub use crate::ViewAttrsExtension;
             pub use crate::_CommonAttrsExtension;
             pub use crate::_CommonCompositeAttrsExtension;
             pub use crate::_InputAttrsExtension;
+            pub use crate::_ScrollViewAttrsExtension;
             pub use crate::_TextAttrsExtension;
             pub use crate::_ViewAttrsExtension;
         }
 
         pub mod events {
+            use crate::animation::AnimationEndEvent;
             use crate::impl_events;
+            use crate::ui_events::{
+                scroll_nearest_scroll_view, DoubleClick, ScrollEvent, TextChangeEvent,
+                TextInputEvent, TextSubmitEvent, WheelEvent,
+            };
 
             impl_events![
                 onmouseover: Pointer<Over>,
@@ -117,6 +149,7 @@ pub mod prelude {
                 onmousedown: Pointer<Down>,
                 onmouseup: Pointer<Up>,
                 onclick: Pointer<Click>,
+                ondblclick: Pointer<DoubleClick>,
                 onmousemove: Pointer<Move>,
                 ondragstart: Pointer<DragStart>,
                 ondrag: Pointer<Drag>,
@@ -124,7 +157,13 @@ pub mod prelude {
                 ondragenter: Pointer<DragEnter>,
                 ondragover: Pointer<DragOver>,
                 ondragleave: Pointer<DragLeave>,
-                ondrop: Pointer<Drop>
+                ondrop: Pointer<Drop>,
+                onwheel: WheelEvent => scroll_nearest_scroll_view,
+                onscroll: ScrollEvent,
+                oninput: TextInputEvent,
+                onchange: TextChangeEvent,
+                onsubmit: TextSubmitEvent,
+                onanimationend: AnimationEndEvent
             ];
         }
     }
@@ -0,0 +1,525 @@
+//! CSS-like stylesheets, rules select elements by tag, class and `name` and set their attrs.
+//!
+//! An attr set explicitly or by a class wins over the stylesheet, the stylesheet value comes back
+//! once neither sets it anymore. Shorthand attrs like `padding` are not supported in rules.
+
+use std::ops::DerefMut;
+
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::ecs::event::ManualEventReader;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy::reflect::{TypePath, TypeUuid};
+use bevy::utils::{BoxedFuture, HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+
+use crate::dom_commands::DomAttributeValue;
+use crate::entity_extra_data::EntitiesExtraData;
+use crate::{get_element_type, ElementRegistry, SetAttrValueContext};
+
+/// Attr value of a rule, converted like the values written in rsx
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum StyleValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl StyleValue {
+    fn to_attr_value(&self) -> DomAttributeValue {
+        match self {
+            StyleValue::Bool(value) => DomAttributeValue::Bool(*value),
+            StyleValue::Number(value) => DomAttributeValue::Float(*value),
+            StyleValue::Text(value) => DomAttributeValue::Text(value.clone()),
+        }
+    }
+}
+
+impl From<bool> for StyleValue {
+    fn from(value: bool) -> Self {
+        StyleValue::Bool(value)
+    }
+}
+
+impl From<f64> for StyleValue {
+    fn from(value: f64) -> Self {
+        StyleValue::Number(value)
+    }
+}
+
+impl From<&str> for StyleValue {
+    fn from(value: &str) -> Self {
+        StyleValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for StyleValue {
+    fn from(value: String) -> Self {
+        StyleValue::Text(value)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StyleRule {
+    /// `view.card`, `#sidebar text`, `.toolbar view, .menu view`
+    pub selector: String,
+    /// values by attr name, `"background": "#ff8800"`, `"padding_left": "8px"`, `"flex_grow": 1.0`
+    pub attrs: HashMap<String, StyleValue>,
+}
+
+/// Loaded from `*.uss` / `*.uss.ron` assets:
+///
+/// ```ron
+/// (
+///     rules: [
+///         (selector: "view.card", attrs: {"background": "#ffffff", "padding_left": "8px"}),
+///         (selector: "#sidebar text", attrs: {"text_color": "gray-700"}),
+///     ],
+/// )
+/// ```
+#[derive(TypeUuid, TypePath, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[uuid = "3b0f6f8e-7f52-4c1e-9d2a-6a4c5e8b9f21"]
+#[serde(default)]
+pub struct UiStyleSheet {
+    pub rules: Vec<StyleRule>,
+}
+
+impl UiStyleSheet {
+    pub fn with_rule<'a>(
+        mut self,
+        selector: impl Into<String>,
+        attrs: impl IntoIterator<Item = (&'a str, StyleValue)>,
+    ) -> Self {
+        self.rules.push(StyleRule {
+            selector: selector.into(),
+            attrs: attrs
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        });
+        self
+    }
+}
+
+/// `view.card#title`, every part is optional
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CompoundSelector {
+    tag: Option<String>,
+    name: Option<String>,
+    classes: Vec<String>,
+}
+
+impl CompoundSelector {
+    fn parse(s: &str) -> Option<Self> {
+        let mut r = Self::default();
+        let tag_end = s.find(['.', '#']).unwrap_or(s.len());
+        match &s[..tag_end] {
+            "" | "*" => {}
+            tag => r.tag = Some(tag.to_string()),
+        }
+        let mut rest = &s[tag_end..];
+        while !rest.is_empty() {
+            let (kind, body) = rest.split_at(1);
+            let end = body.find(['.', '#']).unwrap_or(body.len());
+            let value = &body[..end];
+            if value.is_empty() {
+                return None;
+            }
+            if kind == "." {
+                r.classes.push(value.to_string());
+            } else if r.name.replace(value.to_string()).is_some() {
+                return None;
+            }
+            rest = &body[end..];
+        }
+        Some(r)
+    }
+
+    fn matches(
+        &self,
+        world: &World,
+        entities_extra_data: &EntitiesExtraData,
+        entity: Entity,
+    ) -> bool {
+        let Some(entity_extra_data) = entities_extra_data.get(&entity) else {
+            return false;
+        };
+        self.tag
+            .as_deref()
+            .map_or(true, |tag| tag == entity_extra_data.schema_name)
+            && self.name.as_deref().map_or(true, |name| {
+                world.get::<Name>(entity).is_some_and(|n| n.as_str() == name)
+            })
+            && self.classes.iter().all(|class| {
+                entity_extra_data
+                    .classes
+                    .split_whitespace()
+                    .any(|n| n == class)
+            })
+    }
+}
+
+/// Compound selectors joined by descendant combinators, `#sidebar view.item text`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleSelector {
+    /// outermost ancestor first, the selected element last
+    compounds: Vec<CompoundSelector>,
+}
+
+impl StyleSelector {
+    pub fn parse(s: &str) -> Option<Self> {
+        let compounds = s
+            .split_whitespace()
+            .map(CompoundSelector::parse)
+            .collect::<Option<Vec<_>>>()?;
+        (!compounds.is_empty()).then_some(Self { compounds })
+    }
+
+    /// Like css, `name` selectors first, then classes, then tags
+    pub fn specificity(&self) -> (usize, usize, usize) {
+        self.compounds.iter().fold((0, 0, 0), |(names, classes, tags), n| {
+            (
+                names + n.name.is_some() as usize,
+                classes + n.classes.len(),
+                tags + n.tag.is_some() as usize,
+            )
+        })
+    }
+
+    pub fn matches(
+        &self,
+        world: &World,
+        entities_extra_data: &EntitiesExtraData,
+        entity: Entity,
+    ) -> bool {
+        let Some((subject, ancestors)) = self.compounds.split_last() else {
+            return false;
+        };
+        if !subject.matches(world, entities_extra_data, entity) {
+            return false;
+        }
+        let mut ancestor = world.get::<Parent>(entity).map(|n| n.get());
+        for compound in ancestors.iter().rev() {
+            loop {
+                let Some(current) = ancestor else {
+                    return false;
+                };
+                ancestor = world.get::<Parent>(current).map(|n| n.get());
+                if compound.matches(world, entities_extra_data, current) {
+                    break;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Stylesheets applied to the elements, with the same specificity later rules win
+#[derive(Resource, Clone, Debug, Default, Deref, DerefMut)]
+pub struct UiStyleSheets(pub Vec<Handle<UiStyleSheet>>);
+
+#[derive(Default)]
+pub struct UiStyleSheetLoader;
+
+impl AssetLoader for UiStyleSheetLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let stylesheet: UiStyleSheet = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(stylesheet));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["uss", "uss.ron"]
+    }
+}
+
+struct CompiledStyleRule {
+    selector: StyleSelector,
+    attrs: Vec<(String, StyleValue)>,
+}
+
+type ChangedElements = Query<'static, 'static, Entity, Or<(Changed<Parent>, Changed<Name>)>>;
+
+#[derive(Default)]
+pub struct StyleSheetsState {
+    asset_events: ManualEventReader<AssetEvent<UiStyleSheet>>,
+    /// elements inserted, moved or renamed since the last run
+    changed_elements: Option<SystemState<ChangedElements>>,
+    /// sorted by specificity, then by order in the stylesheets
+    rules: Vec<CompiledStyleRule>,
+    /// attr values already warned about
+    invalid_values: HashSet<(String, String)>,
+}
+
+fn compile_rules(world: &World) -> Vec<CompiledStyleRule> {
+    let (Some(stylesheets), Some(assets)) = (
+        world.get_resource::<UiStyleSheets>(),
+        world.get_resource::<Assets<UiStyleSheet>>(),
+    ) else {
+        return vec![];
+    };
+    let mut rules = vec![];
+    for stylesheet in stylesheets.iter().filter_map(|handle| assets.get(handle)) {
+        for rule in stylesheet.rules.iter() {
+            for selector in rule.selector.split(',') {
+                let Some(selector) = StyleSelector::parse(selector) else {
+                    warn!("invalid stylesheet selector `{}`", selector.trim());
+                    continue;
+                };
+                rules.push(CompiledStyleRule {
+                    selector,
+                    attrs: rule
+                        .attrs
+                        .iter()
+                        .map(|(name, value)| (name.clone(), value.clone()))
+                        .collect(),
+                });
+            }
+        }
+    }
+    rules.sort_by_key(|rule| rule.selector.specificity());
+    rules
+}
+
+/// The entities with all their descendants, selectors match against ancestors
+fn with_descendants(world: &World, entities: HashSet<Entity>) -> HashSet<Entity> {
+    let mut stack = entities.iter().copied().collect::<Vec<_>>();
+    let mut r = entities;
+    while let Some(entity) = stack.pop() {
+        let Some(children) = world.get::<Children>(entity) else {
+            continue;
+        };
+        for child in children.iter() {
+            if r.insert(*child) {
+                stack.push(*child);
+            }
+        }
+    }
+    r
+}
+
+/// Matches the elements whose class, name or parent changed against the stylesheets, every
+/// element when the stylesheets change
+pub fn apply_stylesheets(world: &mut World, mut state: Local<StyleSheetsState>) {
+    let state = state.deref_mut();
+    let mut recompile = world.is_resource_changed::<UiStyleSheets>();
+    if let Some(events) = world.get_resource::<Events<AssetEvent<UiStyleSheet>>>() {
+        recompile |= state.asset_events.iter(events).count() > 0;
+    }
+    let mut dirty = std::mem::take(
+        &mut world
+            .resource_mut::<EntitiesExtraData>()
+            .bypass_change_detection()
+            .stylesheet_dirty,
+    );
+    let changed_elements = state
+        .changed_elements
+        .get_or_insert_with(|| SystemState::new(world));
+    dirty.extend(changed_elements.get(world).iter());
+    if recompile {
+        state.rules = compile_rules(world);
+    } else if dirty.is_empty() {
+        return;
+    }
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let element_registry = world.resource::<ElementRegistry>().clone();
+    world.resource_scope(|world, mut entities_extra_data: Mut<EntitiesExtraData>| {
+        // applying doesn't change the dom
+        let entities_extra_data = entities_extra_data.bypass_change_detection();
+        let entities = if recompile {
+            entities_extra_data.keys().copied().collect::<Vec<_>>()
+        } else {
+            with_descendants(world, dirty).into_iter().collect()
+        };
+        for entity in entities {
+            if world.get_entity(entity).is_none() || !entities_extra_data.contains_key(&entity) {
+                continue;
+            }
+            let matched = state
+                .rules
+                .iter()
+                .enumerate()
+                .filter(|(_, rule)| rule.selector.matches(world, entities_extra_data, entity))
+                .map(|(index, _)| index as u32)
+                .collect::<SmallVec<[u32; 4]>>();
+            let entity_extra_data = entities_extra_data.get_mut(&entity).unwrap();
+            if !recompile && entity_extra_data.stylesheet_rules == matched {
+                continue;
+            }
+            let schema_type = get_element_type(&element_registry, entity_extra_data.schema_name);
+            let mut values = HashMap::new();
+            for rule in matched.iter().map(|index| &state.rules[*index as usize]) {
+                for (name, value) in rule.attrs.iter() {
+                    // rules may select elements without the attr
+                    let Some(attr) = schema_type.attr(&element_registry, name) else {
+                        continue;
+                    };
+                    let Some(value) = attr.parse_dyn_value(value.to_attr_value()) else {
+                        if state.invalid_values.insert((name.clone(), format!("{:?}", value))) {
+                            warn!("stylesheet value {:?} of `{}` is invalid", value, name);
+                        }
+                        continue;
+                    };
+                    values.insert(attr.index(), value);
+                }
+            }
+            entity_extra_data.stylesheet_rules = matched;
+            let previous =
+                std::mem::replace(&mut entity_extra_data.stylesheet_values, values.clone());
+            let attr_is_set = entity_extra_data.attr_is_set | entity_extra_data.class_attr_is_set;
+            let mut context = SetAttrValueContext {
+                entity_ref: &mut world.entity_mut(entity),
+                entities_extra_data,
+                type_registry: type_registry.clone(),
+                element_registry: element_registry.clone(),
+            };
+            for (index, value) in values.into_iter() {
+                if attr_is_set.contains(index) {
+                    continue;
+                }
+                if let Some(attr) = schema_type.attr_by_index(&element_registry, index) {
+                    attr.set_dyn_value(&mut context, value);
+                }
+            }
+            for index in previous.into_keys() {
+                if attr_is_set.contains(index)
+                    || context.entity_extra_data().stylesheet_values.contains_key(&index)
+                {
+                    continue;
+                }
+                if let Some(attr) = schema_type.attr_by_index(&element_registry, index) {
+                    attr.set_to_default_value(&mut context);
+                }
+            }
+        }
+    });
+}
+
+pub trait UiStyleSheetAppExt {
+    /// Loads the stylesheet asset, it is applied again on every hot reload
+    fn load_ui_stylesheet(&mut self, path: &str) -> &mut Self;
+    fn add_ui_stylesheet(&mut self, stylesheet: UiStyleSheet) -> &mut Self;
+}
+
+impl UiStyleSheetAppExt for App {
+    fn load_ui_stylesheet(&mut self, path: &str) -> &mut Self {
+        let handle = self.world.resource::<AssetServer>().load(path);
+        self.world
+            .get_resource_or_insert_with(UiStyleSheets::default)
+            .push(handle);
+        self
+    }
+
+    fn add_ui_stylesheet(&mut self, stylesheet: UiStyleSheet) -> &mut Self {
+        let handle = self
+            .world
+            .resource_mut::<Assets<UiStyleSheet>>()
+            .add(stylesheet);
+        self.world
+            .get_resource_or_insert_with(UiStyleSheets::default)
+            .push(handle);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Deref;
+
+    use bevy::prelude::*;
+
+    use super::StyleSelector;
+    use crate::entity_extra_data::{EntitiesExtraData, EntityExtraData};
+    use crate::smallbox;
+
+    fn spawn_element(
+        world: &mut World,
+        entities_extra_data: &mut EntitiesExtraData,
+        tag: &'static str,
+        classes: &str,
+        parent: Option<Entity>,
+    ) -> Entity {
+        let entity = world.spawn_empty().id();
+        if let Some(parent) = parent {
+            world.entity_mut(parent).push_children(&[entity]);
+        }
+        let mut entity_extra_data = EntityExtraData::new(tag);
+        entity_extra_data.classes = classes.to_string();
+        entities_extra_data.insert(entity, entity_extra_data);
+        entity
+    }
+
+    #[test]
+    fn parse_selector() {
+        assert!(StyleSelector::parse("view.card#title .item text").is_some());
+        assert!(StyleSelector::parse("*").is_some());
+        assert!(StyleSelector::parse("view.").is_none());
+        assert!(StyleSelector::parse("#a#b").is_none());
+        assert!(StyleSelector::parse("  ").is_none());
+    }
+
+    #[test]
+    fn specificity() {
+        let specificity = |s| StyleSelector::parse(s).unwrap().specificity();
+        assert_eq!(specificity("view"), (0, 0, 1));
+        assert_eq!(specificity("#sidebar view.item.active text"), (1, 2, 2));
+        assert!(specificity(".card") > specificity("scroll_view view text"));
+    }
+
+    #[test]
+    fn descendant_matching() {
+        let mut world = World::new();
+        let mut data = EntitiesExtraData::default();
+        let sidebar = spawn_element(&mut world, &mut data, "view", "panel", None);
+        world.entity_mut(sidebar).insert(Name::new("sidebar"));
+        let item = spawn_element(&mut world, &mut data, "view", "item active", Some(sidebar));
+        let label = spawn_element(&mut world, &mut data, "text", "", Some(item));
+        let other = spawn_element(&mut world, &mut data, "text", "", None);
+        let matches = |selector: &str, entity| {
+            StyleSelector::parse(selector)
+                .unwrap()
+                .matches(&world, &data, entity)
+        };
+
+        assert!(matches("#sidebar", sidebar));
+        assert!(!matches("#sidebar", item));
+        assert!(matches("#sidebar text", label));
+        assert!(!matches("#sidebar text", other));
+        assert!(matches("view.item text", label));
+        assert!(matches("view.panel view.item.active text", label));
+        assert!(matches(".panel text", label));
+        assert!(!matches(".item #sidebar text", label));
+        assert!(!matches("view.item.hidden text", label));
+        assert!(!matches("text view", item));
+    }
+
+    #[test]
+    fn explicit_over_class_over_stylesheet() {
+        let index = 1;
+        let fallback = |data: &EntityExtraData| {
+            data.fallback_value(index)
+                .and_then(|n| n.deref().as_reflect().downcast_ref::<f32>().copied())
+        };
+        let mut data = EntityExtraData::new("view");
+        assert_eq!(fallback(&data), None);
+        data.stylesheet_values.insert(index, smallbox!(1.0f32));
+        assert_eq!(fallback(&data), Some(1.0));
+        data.normal_props_map.insert(index, smallbox!(2.0f32));
+        data.set_class_attr(index, true);
+        assert_eq!(fallback(&data), Some(2.0));
+        data.set_attr(index, true);
+        assert_eq!(fallback(&data), None);
+        data.set_attr(index, false);
+        assert_eq!(fallback(&data), Some(2.0));
+        data.set_class_attr(index, false);
+        assert_eq!(fallback(&data), Some(1.0));
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use bevy::prelude::Resource;
+use bevy::utils::HashMap;
+
+use crate::tailwind::{expand_classes, parse_theme_class, TailwindClassItem, TailwindTheme};
+
+/// Entries kept before the cache is cleared, class strings built with `format!` could grow it forever
+const MAX_CACHED_CLASS_STRINGS: usize = 4096;
+
+/// Classes of one `class` attr value, in the order they are applied
+#[derive(Default)]
+pub struct ParsedClasses {
+    pub items: Vec<TailwindClassItem>,
+    /// contains the `group` class
+    pub has_group: bool,
+    /// classes that set nothing, see [`TailwindDiagnostics`](crate::tailwind::TailwindDiagnostics)
+    pub unknown: Vec<String>,
+}
+
+impl ParsedClasses {
+    pub fn parse(theme: &TailwindTheme, classes: &str) -> Self {
+        let mut items = vec![];
+        let mut has_group = false;
+        let mut unknown = vec![];
+        expand_classes(theme, classes, &mut |class| {
+            if class == "group" {
+                has_group = true;
+                return;
+            }
+            let item = parse_theme_class(theme, class);
+            if item.0.is_empty() {
+                unknown.push(class.to_string());
+            } else {
+                items.push((items.len(), item));
+            }
+        });
+        // variant classes are applied after plain ones, higher states win
+        items.sort_by_key(|(index, n)| (n.1.bits(), *index));
+        Self {
+            items: items.into_iter().map(|(_, n)| n).collect(),
+            has_group,
+            unknown,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct ClassCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// distinct class strings currently cached
+    pub entries: usize,
+}
+
+#[derive(Default)]
+struct TailwindClassCacheInner {
+    entries: RwLock<HashMap<Box<str>, Arc<ParsedClasses>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Parsed classes by class string, shared by the elements and the templates of one `App`.
+/// Cloning shares the same cache, it is cleared when the theme changes.
+#[derive(Resource, Clone, Default)]
+pub struct TailwindClassCache {
+    inner: Arc<TailwindClassCacheInner>,
+}
+
+impl TailwindClassCache {
+    /// `theme` is the one the cache was last cleared for, the `TailwindTheme` resource
+    pub fn get_or_parse(&self, theme: &TailwindTheme, classes: &str) -> Arc<ParsedClasses> {
+        if let Some(parsed) = self.inner.entries.read().unwrap().get(classes) {
+            self.inner.hits.fetch_add(1, Ordering::Relaxed);
+            return parsed.clone();
+        }
+        self.inner.misses.fetch_add(1, Ordering::Relaxed);
+        let parsed = Arc::new(ParsedClasses::parse(theme, classes));
+        let mut entries = self.inner.entries.write().unwrap();
+        if entries.len() >= MAX_CACHED_CLASS_STRINGS {
+            entries.clear();
+        }
+        entries.insert(classes.into(), parsed.clone());
+        parsed
+    }
+
+    pub fn clear(&self) {
+        self.inner.entries.write().unwrap().clear();
+    }
+
+    pub fn stats(&self) -> ClassCacheStats {
+        ClassCacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            entries: self.inner.entries.read().unwrap().len(),
+        }
+    }
+
+    pub fn reset_stats(&self) {
+        self.inner.hits.store(0, Ordering::Relaxed);
+        self.inner.misses.store(0, Ordering::Relaxed);
+    }
+}
@@ -1,17 +1,10 @@
 use std::borrow::Cow;
 
 use bevy::{prelude::Color, utils::HashMap};
-use lazy_static::lazy_static;
 
-type Colors = HashMap<Cow<'static, str>, HashMap<Cow<'static, str>, [u8; 4]>>;
+use crate::tailwind::{arbitrary_value, TailwindTheme};
 
-lazy_static! {
-    static ref COLORS: Colors = {
-        let mut colors = Colors::new();
-        insert_default_colors(&mut colors);
-        colors
-    };
-}
+pub type Colors = HashMap<Cow<'static, str>, HashMap<Cow<'static, str>, [u8; 4]>>;
 
 pub fn insert_default_colors(colors: &mut Colors) {
     colors.insert(
@@ -413,41 +406,113 @@ pub fn insert_default_colors(colors: &mut Colors) {
 }
 
 pub fn parse_color(class: &str) -> Option<Color> {
-    // Split the class into components
-    let components: Vec<&str> = class.split('/').collect();
-    let color_and_variant: Vec<&str> = components[0].split('-').collect();
+    parse_theme_color(TailwindTheme::builtin(), class)
+}
 
-    // If there's an alpha channel specified, get it
-    let alpha = if components.len() > 1 {
-        match components[1].parse::<u16>() {
-            // convert from 100 to 255k
-            Ok(a) => (a * 255 / 100) as u8,
-            Err(_) => return None, // Invalid alpha
+pub fn parse_theme_color(theme: &TailwindTheme, class: &str) -> Option<Color> {
+    let (name, modifier) = split_modifier(class);
+    let mut color = parse_color_name(theme, name)?;
+    if let Some(modifier) = modifier {
+        color.set_a(parse_alpha_modifier(modifier)?);
+    }
+    Some(color)
+}
+
+/// `red-500/50` → (`red-500`, `50`), slashes inside `[]` or `()` are part of the value
+fn split_modifier(class: &str) -> (&str, Option<&str>) {
+    let mut depth = 0;
+    let mut split_index = None;
+    for (index, char) in class.char_indices() {
+        match char {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            '/' if depth == 0 => split_index = Some(index),
+            _ => {}
         }
-    } else {
-        255 // Default alpha
-    };
+    }
+    match split_index {
+        Some(index) => (&class[..index], Some(&class[index + 1..])),
+        None => (class, None),
+    }
+}
+
+/// `50` is a percentage, `[0.35]` / `[35%]` arbitrary
+fn parse_alpha_modifier(modifier: &str) -> Option<f32> {
+    match arbitrary_value(modifier) {
+        Some(value) => parse_fraction(value),
+        None => modifier.parse::<u16>().ok().map(|n| n.min(100) as f32 / 100.0),
+    }
+}
 
-    // Handle special colors
-    if color_and_variant.len() == 1 {
-        return match color_and_variant[0] {
-            "transparent" => Some(Color::rgba_u8(0, 0, 0, 0)),
-            "white" => Some(Color::rgba_u8(255, 255, 255, alpha)),
-            "black" => Some(Color::rgba_u8(0, 0, 0, alpha)),
-            _ => COLORS.get(color_and_variant[0]).map(|c| {
-                let (_, variant) = c.iter().next().unwrap();
-                Color::rgba_u8(variant[0], variant[1], variant[2], alpha)
-            }),
-        };
+fn parse_color_name(theme: &TailwindTheme, name: &str) -> Option<Color> {
+    if let Some(value) = arbitrary_value(name) {
+        return parse_css_color(&value.replace('_', " "));
     }
+    if name.starts_with('#') || name.contains('(') {
+        return parse_css_color(name);
+    }
+    // Custom colors may contain dashes, `bg-brand-dark`
+    if let Some(&[r, g, b, a]) = theme.custom_colors.get(name) {
+        return Some(Color::rgba_u8(r, g, b, a));
+    }
+    match name {
+        "transparent" => Some(Color::rgba_u8(0, 0, 0, 0)),
+        "white" => Some(Color::rgba_u8(255, 255, 255, 255)),
+        "black" => Some(Color::rgba_u8(0, 0, 0, 255)),
+        name => {
+            let [r, g, b, a] = match name.split_once('-') {
+                Some((color, variant)) => theme.color(color, Some(variant))?,
+                None => theme.color(name, None)?,
+            };
+            Some(Color::rgba_u8(r, g, b, a))
+        }
+    }
+}
 
-    // Handle regular colors
-    let color = color_and_variant[0];
-    let variant = color_and_variant[1];
+/// `#f80`, `#ff8800cc`, `rgb(255, 136, 0)`, `rgba(255 136 0 / 50%)`, `hsl(30deg, 100%, 50%)`
+pub fn parse_css_color(s: &str) -> Option<Color> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix('#') {
+        return Color::hex(hex).ok();
+    }
+    let (function, args) = s.strip_suffix(')')?.split_once('(')?;
+    let args = args
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|n| !n.is_empty())
+        .collect::<Vec<_>>();
+    let alpha = match args.get(3) {
+        Some(alpha) => parse_fraction(alpha)?,
+        None => 1.0,
+    };
+    match (function.trim(), &args[..]) {
+        ("rgb" | "rgba", [r, g, b, ..]) => Some(Color::rgba(
+            parse_rgb_channel(r)?,
+            parse_rgb_channel(g)?,
+            parse_rgb_channel(b)?,
+            alpha,
+        )),
+        ("hsl" | "hsla", [h, s, l, ..]) => Some(Color::hsla(
+            h.trim_end_matches("deg").parse().ok()?,
+            parse_fraction(s)?,
+            parse_fraction(l)?,
+            alpha,
+        )),
+        _ => None,
+    }
+}
+
+/// `0.5` or `50%`
+fn parse_fraction(s: &str) -> Option<f32> {
+    match s.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok().map(|n| n / 100.0),
+        None => s.parse().ok(),
+    }
+}
 
-    COLORS.get(color).and_then(|variants| {
-        variants
-            .get(variant)
-            .map(|&[r, g, b, _]| Color::rgba_u8(r, g, b, alpha))
-    })
+/// `0`..`255` or `0%`..`100%`
+fn parse_rgb_channel(s: &str) -> Option<f32> {
+    match s.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok().map(|n| n / 100.0),
+        None => s.parse::<f32>().ok().map(|n| n / 255.0),
+    }
 }
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::tailwind::{ParsedClasses, TailwindTheme};
+
+/// Sent for each class of an element that sets nothing, when enabled in [`TailwindDiagnostics`]
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct UnknownClassWarning {
+    pub element: Entity,
+    pub tag: &'static str,
+    pub class: String,
+}
+
+/// How unknown or malformed classes are reported, templates report once they are instantiated.
+#[derive(Resource, Debug, Clone)]
+pub struct TailwindDiagnostics {
+    /// `warn!` once per tag and class, on by default in debug builds
+    pub log_unknown_classes: bool,
+    /// sends [`UnknownClassWarning`] for every element
+    pub unknown_class_events: bool,
+    logged: HashSet<(&'static str, String)>,
+}
+
+impl Default for TailwindDiagnostics {
+    fn default() -> Self {
+        Self {
+            log_unknown_classes: cfg!(debug_assertions),
+            unknown_class_events: false,
+            logged: default(),
+        }
+    }
+}
+
+impl TailwindDiagnostics {
+    pub fn with_logging(mut self, enabled: bool) -> Self {
+        self.log_unknown_classes = enabled;
+        self
+    }
+
+    pub fn with_events(mut self, enabled: bool) -> Self {
+        self.unknown_class_events = enabled;
+        self
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.log_unknown_classes || self.unknown_class_events
+    }
+}
+
+pub fn report_unknown_classes(
+    world: &mut World,
+    element: Entity,
+    tag: &'static str,
+    classes: &[String],
+) {
+    if classes.is_empty() {
+        return;
+    }
+    let Some(mut diagnostics) = world.get_resource_mut::<TailwindDiagnostics>() else {
+        return;
+    };
+    if diagnostics.log_unknown_classes {
+        for class in classes {
+            if diagnostics.logged.insert((tag, class.clone())) {
+                warn!("unknown class `{}` on `{}` element", class, tag);
+            }
+        }
+    }
+    if !diagnostics.unknown_class_events {
+        return;
+    }
+    if let Some(mut events) = world.get_resource_mut::<Events<UnknownClassWarning>>() {
+        for class in classes {
+            events.send(UnknownClassWarning {
+                element,
+                tag,
+                class: class.clone(),
+            });
+        }
+    }
+}
+
+/// Classes that set nothing with the default theme, for tests:
+/// `assert_eq!(validate_classes("p-2 grow"), Ok(()))`
+pub fn validate_classes(classes: &str) -> Result<(), Vec<String>> {
+    validate_theme_classes(TailwindTheme::builtin(), classes)
+}
+
+pub fn validate_theme_classes(theme: &TailwindTheme, classes: &str) -> Result<(), Vec<String>> {
+    let unknown = ParsedClasses::parse(theme, classes).unknown;
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(unknown)
+    }
+}
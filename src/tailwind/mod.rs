@@ -1,32 +1,36 @@
 use std::ops::Deref;
+use std::sync::Arc;
 
-use bevy::prelude::{Component, Visibility};
+use bevy::prelude::{Children, Component, Entity, Parent, Resource, World};
 use bevy::reflect::Reflect;
-use bevy::text::BreakLineOn;
 use bevy::ui::*;
-use bevy::utils::{default, HashMap};
+use bevy::utils::HashMap;
+use bevy_cosmic_edit::Focus;
 use smallvec::{smallvec, SmallVec};
 
+pub use cache::*;
 pub use colors::*;
+pub use diagnostics::*;
+pub use theme::*;
+pub use utilities::*;
 
-use crate::{
-    element_attrs, ElementAttrUntyped, ElementTypeUnTyped, from_str, get_element_type,
-    SetAttrValueContext,
+use crate::{ElementAttrUntyped, SetAttrValueContext};
+use crate::entity_extra_data::{
+    AttrSetBits, Checked, Disabled, ElementStates, UiGroup,
 };
-use crate::{smallbox, SmallBox};
-use crate::UiTexture;
-use crate::element_core::AttrValue;
-use crate::entity_extra_data::get_all_prop_indecs;
-use crate::prelude::{TextAlignment, warn};
-use crate::smallbox::S1;
+use crate::prelude::warn;
 
+mod cache;
 mod colors;
+mod diagnostics;
+mod theme;
+mod utilities;
+
+#[cfg(test)]
+mod tests;
 
 #[derive(Default)]
-pub struct TailwindClassItem(
-    pub SmallVec<[(&'static dyn ElementAttrUntyped, SmallBox<dyn AttrValue, S1>); 4]>,
-    pub Interaction,
-);
+pub struct TailwindClassItem(pub ClassAttrs, pub ElementStates);
 
 impl Clone for TailwindClassItem {
     fn clone(&self) -> Self {
@@ -41,57 +45,124 @@ impl Clone for TailwindClassItem {
 #[derive(Component, Reflect, Default, Debug, PartialEq)]
 pub struct InteractionClass;
 
+/// Logical width of the primary window, the `sm:` / `md:` ... variants are resolved against it
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct UiViewportWidth(pub f32);
+
+pub fn breakpoint_states(width: f32, theme: &TailwindTheme) -> ElementStates {
+    let mut states = ElementStates::NONE;
+    for (name, state) in ElementStates::BREAKPOINTS {
+        if theme.breakpoint(name).is_some_and(|min_width| width >= min_width) {
+            states |= state;
+        }
+    }
+    states
+}
+
+pub fn compute_element_states(world: &World, entity: Entity) -> ElementStates {
+    let mut states = ElementStates::NONE;
+    let interaction = world.get::<Interaction>(entity).copied().unwrap_or_default();
+    states.set(
+        ElementStates::HOVER,
+        matches!(interaction, Interaction::Hovered | Interaction::Pressed),
+    );
+    states.set(ElementStates::ACTIVE, interaction == Interaction::Pressed);
+    states.set(
+        ElementStates::FOCUS,
+        world
+            .get_resource::<Focus>()
+            .is_some_and(|focus| focus.0 == Some(entity)),
+    );
+    states.set(ElementStates::DISABLED, world.get::<Disabled>(entity).is_some());
+    states.set(ElementStates::CHECKED, world.get::<Checked>(entity).is_some());
+    if let Some(theme) = world.get_resource::<TailwindTheme>() {
+        if let Some(viewport) = world.get_resource::<UiViewportWidth>() {
+            states |= breakpoint_states(viewport.0, theme);
+        }
+        if let Some(color_scheme) = world.get_resource::<UiColorScheme>() {
+            states |= color_scheme.states(theme);
+        }
+    }
+
+    let parent = world.get::<Parent>(entity).map(|n| n.get());
+    if let Some(children) = parent.and_then(|parent| world.get::<Children>(parent)) {
+        if let Some(index) = children.iter().position(|n| *n == entity) {
+            states.set(ElementStates::FIRST, index == 0);
+            states.set(ElementStates::LAST, index == children.len() - 1);
+            // 1-based like css `nth-child`
+            states.set(ElementStates::ODD, index % 2 == 0);
+            states.set(ElementStates::EVEN, index % 2 == 1);
+        }
+    }
+
+    let mut ancestor = parent;
+    while let Some(current) = ancestor {
+        if world.get::<UiGroup>(current).is_some()
+            && matches!(
+                world.get::<Interaction>(current),
+                Some(Interaction::Hovered | Interaction::Pressed)
+            )
+        {
+            states |= ElementStates::GROUP_HOVER;
+            break;
+        }
+        ancestor = world.get::<Parent>(current).map(|n| n.get());
+    }
+    states
+}
+
+/// Recomputes the states of the element, re-applying variant classes when they changed.
+pub fn update_element_states(context: &mut SetAttrValueContext) {
+    let states = compute_element_states(context.entity_ref.world(), context.entity_ref.id());
+    let entity_extra_data = context.entity_extra_data();
+    if entity_extra_data.states == states {
+        return;
+    }
+    entity_extra_data.states = states;
+    if !entity_extra_data.interaction_classes.is_empty() {
+        handle_interaction_classes(context);
+    }
+}
+
 pub fn handle_classes(context: &mut SetAttrValueContext, classes: &str) {
-    let mut set_bits = 0;
+    let mut set_bits = AttrSetBits::EMPTY;
+    let parsed = {
+        let world = context.entity_ref.world();
+        let theme = world.get_resource::<TailwindTheme>();
+        match (theme, world.get_resource::<TailwindClassCache>()) {
+            (Some(theme), Some(cache)) => cache.get_or_parse(theme, classes),
+            (theme, _) => Arc::new(ParsedClasses::parse(
+                theme.unwrap_or(TailwindTheme::builtin()),
+                classes,
+            )),
+        }
+    };
 
-    let interaction = context
-        .entity_ref
-        .get::<Interaction>()
-        .copied()
-        .unwrap_or_default();
-    let mut interaction_classes = smallvec![];
-    let mut items = classes
-        .split_whitespace()
-        .into_iter()
-        .map(parse_class)
-        .enumerate()
-        .collect::<Vec<_>>();
-    items.sort_by(|(index, n), (other_index, other)| {
-        use std::cmp::Ordering;
-        let left = n.1;
-        let right = other.1;
-        match left {
-            Interaction::Pressed => match right {
-                Interaction::Pressed => index.cmp(other_index),
-                Interaction::Hovered => Ordering::Greater,
-                Interaction::None => Ordering::Greater,
-            },
-            Interaction::Hovered => match right {
-                Interaction::Pressed => Ordering::Less,
-                Interaction::Hovered => index.cmp(other_index),
-                Interaction::None => Ordering::Greater,
-            },
-            Interaction::None => match right {
-                Interaction::Pressed => Ordering::Less,
-                Interaction::Hovered => Ordering::Less,
-                Interaction::None => index.cmp(other_index),
-            },
+    if parsed.has_group {
+        context.entity_ref.insert(UiGroup);
+        if !context.entity_ref.contains::<Interaction>() {
+            context.entity_ref.insert(Interaction::default());
         }
-    });
+    } else {
+        context.entity_ref.remove::<UiGroup>();
+    }
+    let states = compute_element_states(context.entity_ref.world(), context.entity_ref.id());
+    context.entity_extra_data().states = states;
+    let mut interaction_classes = smallvec![];
     let mut normal_props_map = HashMap::new();
-    for (_index, item) in items {
-        if item.1 != Interaction::None {
-            interaction_classes.push(Clone::clone(&item));
+    for item in parsed.items.iter() {
+        if !item.1.is_empty() {
+            interaction_classes.push(Clone::clone(item));
         }
 
-        for (prop, value) in item.0.into_iter() {
-            match (item.1, interaction) {
-                (Interaction::Pressed, Interaction::Pressed)
-                | (Interaction::Hovered, Interaction::Hovered | Interaction::Pressed) => {
+        for (prop, value) in item.0.iter() {
+            let value = value.deref().clone_att_value();
+            match (item.1.is_empty(), states.contains(item.1)) {
+                (false, true) => {
                     prop.set_dyn_value_in_class(context, value);
-                    set_bits |= (1 << prop.index());
+                    set_bits.insert(prop.index());
                 }
-                (Interaction::None, _) => {
+                (true, _) => {
                     /*
                     if let Some(previous_value) = normal_props_map.remove(&prop.index()) {
                         let mut value = value.clone();
@@ -103,7 +174,7 @@ pub fn handle_classes(context: &mut SetAttrValueContext, classes: &str) {
                     */
                     normal_props_map.insert(prop.index(), value.clone());
                     prop.set_dyn_value_in_class(context, value);
-                    set_bits |= (1 << prop.index());
+                    set_bits.insert(prop.index());
                 }
                 _ => {}
             }
@@ -114,50 +185,70 @@ pub fn handle_classes(context: &mut SetAttrValueContext, classes: &str) {
     } else {
         context.entity_ref.remove::<InteractionClass>();
     }
+    let schema_type = context.element_type();
     let entity_extra_data = context.entity_extra_data();
-    let schema_type: &dyn ElementTypeUnTyped = get_element_type(entity_extra_data.schema_name);
 
+    let tag = entity_extra_data.schema_name;
+    let classes_changed = entity_extra_data.classes != classes;
+    if classes_changed {
+        entity_extra_data.classes = classes.to_string();
+    }
     entity_extra_data.interaction_classes = interaction_classes;
     entity_extra_data.normal_props_map = normal_props_map;
     for prop_index in entity_extra_data.iter_class_attr_indices_exclude(set_bits) {
-        let prop = schema_type.attr_by_index(prop_index);
-        prop.set_to_default_value(context);
+        if let Some(prop) = schema_type.attr_by_index(&context.element_registry, prop_index) {
+            reset_class_attr(context, prop);
+        }
+    }
+    if classes_changed {
+        let entity = context.entity_ref.id();
+        context.entities_extra_data.stylesheet_dirty.insert(entity);
+    }
+    if !parsed.unknown.is_empty() {
+        let entity = context.entity_ref.id();
+        context
+            .entity_ref
+            .world_scope(|world| report_unknown_classes(world, entity, tag, &parsed.unknown));
+    }
+}
+
+/// Parses the stored class string again, e.g. after the theme changed
+pub fn reapply_classes(context: &mut SetAttrValueContext) {
+    let classes = context.entity_extra_data().classes.clone();
+    if !classes.is_empty() {
+        handle_classes(context, &classes);
     }
 }
 
 pub fn handle_interaction_classes(context: &mut SetAttrValueContext) {
-    let interaction = context
-        .entity_ref
-        .get::<Interaction>()
-        .copied()
-        .unwrap_or_default();
+    let states = context.entity_extra_data().states;
 
-    let mut set_bits: u64 = 0;
-    let mut unset_bits: u64 = 0;
+    let mut set_bits = AttrSetBits::EMPTY;
+    let mut unset_bits = AttrSetBits::EMPTY;
     let items = Clone::clone(&context.entity_extra_data().interaction_classes);
     for item in items.into_iter() {
         for (prop, value) in item.0.into_iter() {
-            match (item.1, interaction) {
-                (Interaction::Pressed, Interaction::Pressed)
-                | (Interaction::Hovered, Interaction::Hovered | Interaction::Pressed) => {
+            match (item.1.is_empty(), states.contains(item.1)) {
+                (false, true) => {
                     prop.set_dyn_value_in_class(context, value);
-                    set_bits |= (1 << prop.index());
+                    set_bits.insert(prop.index());
                 }
-                (Interaction::None, _) => {
+                (true, _) => {
                     warn!("This is not a interaction class!")
                 }
                 _ => {
-                    unset_bits |= (1 << prop.index());
+                    unset_bits.insert(prop.index());
                 }
             }
         }
     }
-    let schema_type: &dyn ElementTypeUnTyped =
-        get_element_type(context.entity_extra_data().schema_name);
+    let schema_type = context.element_type();
 
-    let num = unset_bits & (!set_bits) & (!context.entity_extra_data().attr_is_set);
-    for prop_index in get_all_prop_indecs().filter(move |i| (num >> i) & 1 == 1) {
-        let prop = schema_type.attr_by_index(prop_index);
+    let bits = unset_bits & (!set_bits) & (!context.entity_extra_data().attr_is_set);
+    for prop_index in bits.iter() {
+        let Some(prop) = schema_type.attr_by_index(&context.element_registry, prop_index) else {
+            continue;
+        };
         if let Some(value) = context
             .entity_extra_data()
             .normal_props_map
@@ -166,361 +257,71 @@ pub fn handle_interaction_classes(context: &mut SetAttrValueContext) {
         {
             prop.set_dyn_value(context, value);
         } else {
-            prop.set_to_default_value(context);
+            reset_class_attr(context, prop);
         }
     }
 }
 
-pub fn parse_size_val(text: &str) -> Val {
-    match text {
-        "full" => Val::Percent(1.0),
-        "auto" => Val::Auto,
-        class => {
-            if class.ends_with('%') {
-                Val::Percent(
-                    class
-                        .strip_suffix('%')
-                        .unwrap()
-                        .parse::<f32>()
-                        .unwrap_or(0.0)
-                        / 100.0,
-                )
-            } else if class.ends_with("px") {
-                Val::Px(class.parse::<f32>().unwrap_or(0.0))
-            } else {
-                Val::Px(class.parse::<f32>().unwrap_or(0.0) * 4.0)
-            }
-        }
+/// An attr no class sets anymore falls back to the stylesheet value or the default
+fn reset_class_attr(context: &mut SetAttrValueContext, prop: &dyn ElementAttrUntyped) {
+    let entity_extra_data = context.entity_extra_data();
+    entity_extra_data.set_class_attr(prop.index(), false);
+    match entity_extra_data.stylesheet_values.get(&prop.index()).cloned() {
+        Some(value) => prop.set_dyn_value(context, value),
+        None => prop.set_to_default_value(context),
     }
 }
 
-fn parse_class_inner<'a>(
-    class: &'a str,
-) -> SmallVec<[(&'static dyn ElementAttrUntyped, SmallBox<dyn AttrValue, S1>); 4]> {
-    match class {
-        "visible" => smallvec![(
-            &element_attrs::visibility as _,
-            smallbox!(Visibility::Visible)
-        ),],
-        "invisible" => smallvec![(
-            &element_attrs::visibility as _,
-            smallbox!(Visibility::Hidden)
-        ),],
-        "flex-col" => smallvec![
-            (&element_attrs::display as _, smallbox!(Display::Flex)),
-            (
-                &element_attrs::flex_direction as _,
-                smallbox!(FlexDirection::Column)
-            )
-        ],
-        "flex-row" => smallvec![
-            (&element_attrs::display as _, smallbox!(Display::Flex)),
-            (
-                &element_attrs::flex_direction as _,
-                smallbox!(FlexDirection::Row)
-            )
-        ],
-        "grid" => smallvec![(&element_attrs::display as _, smallbox!(Display::Grid)),],
-        "shrink" => smallvec![(&element_attrs::flex_shrink as _, smallbox!(1.0)),],
-        "shrink-0" => smallvec![(&element_attrs::flex_shrink as _, smallbox!(0.0)),],
-        "grow" => smallvec![(&element_attrs::flex_grow as _, smallbox!(1.0)),],
-        "grow-0" => smallvec![(&element_attrs::flex_grow as _, smallbox!(0.0)),],
-        class => {
-            if let Some(index) = class.strip_prefix("z-") {
-                if let Ok(index) = index.parse() {
-                    smallvec![(
-                        &element_attrs::z_index as _,
-                        smallbox!(ZIndex::Global(index))
-                    ),]
-                } else {
-                    default()
-                }
-            } else if let Some(class) = class.strip_prefix("justify-") {
-                smallvec![(
-                    &element_attrs::justify_content as _,
-                    smallbox!(match class {
-                        "start" => JustifyContent::Start,
-                        "end" => JustifyContent::End,
-                        "center" => JustifyContent::Center,
-                        "between" => JustifyContent::SpaceBetween,
-                        "around" => JustifyContent::SpaceAround,
-                        "evenly" => JustifyContent::SpaceEvenly,
-                        _ => {
-                            JustifyContent::Default
-                        }
-                    })
-                ),]
-            } else if let Some(class) = class.strip_prefix("items-") {
-                smallvec![(
-                    &element_attrs::align_items as _,
-                    smallbox!(match class {
-                        "start" => AlignItems::FlexStart,
-                        "end" => AlignItems::FlexEnd,
-                        "center" => AlignItems::Center,
-                        "baseline" => AlignItems::Baseline,
-                        "stretch" => AlignItems::Stretch,
-                        _ => {
-                            AlignItems::Default
-                        }
-                    })
-                ),]
-            } else if let Some(class) = class.strip_prefix("gap-") {
-                let gap = parse_size_val(class);
-                smallvec![
-                    (&element_attrs::column_gap as _, smallbox!(gap)),
-                    (&element_attrs::row_gap as _, smallbox!(gap)),
-                ]
-            } else if let Some(class) = class.strip_prefix("gap-x-") {
-                let gap = parse_size_val(class);
-                smallvec![(&element_attrs::row_gap as _, smallbox!(gap)),]
-            } else if let Some(class) = class.strip_prefix("gap-y-") {
-                let gap = parse_size_val(class);
-                smallvec![(&element_attrs::column_gap as _, smallbox!(gap)),]
-            } else if class == "relative" {
-                smallvec![(
-                    &element_attrs::position_type as _,
-                    smallbox!(PositionType::Relative)
-                ),]
-            } else if class == "absolute" {
-                smallvec![(
-                    &element_attrs::position_type as _,
-                    smallbox!(PositionType::Absolute)
-                ),]
-            } else if class == "hidden" {
-                smallvec![(&element_attrs::display as _, smallbox!(Display::None)),]
-            } else if let Some(class) = class.strip_prefix("left-") {
-                smallvec![(&element_attrs::left as _, smallbox!(parse_size_val(class))),]
-            } else if let Some(class) = class.strip_prefix("right-") {
-                smallvec![(&element_attrs::right as _, smallbox!(parse_size_val(class))),]
-            } else if let Some(class) = class.strip_prefix("top-") {
-                smallvec![(&element_attrs::top as _, smallbox!(parse_size_val(class))),]
-            } else if let Some(class) = class.strip_prefix("bottom-") {
-                smallvec![(
-                    &element_attrs::bottom as _,
-                    smallbox!(parse_size_val(class))
-                ),]
-            } else if let Some(class) = class.strip_prefix("flex-") {
-                if let Some(val) = (match class {
-                    "wrap" => Some(FlexWrap::Wrap),
-                    "wrap-reverse" => Some(FlexWrap::WrapReverse),
-                    "nowrap" => Some(FlexWrap::NoWrap),
-                    _ => None,
-                }) {
-                    smallvec![(&element_attrs::flex_wrap as _, smallbox!(val))]
-                } else {
-                    default()
-                }
-            } else if let Some(class) = class.strip_prefix("w-") {
-                smallvec![(&element_attrs::width as _, smallbox!(parse_size_val(class))),]
-            } else if let Some(class) = class.strip_prefix("h-") {
-                smallvec![(
-                    &element_attrs::height as _,
-                    smallbox!(parse_size_val(class))
-                ),]
-            } else if let Some(class) = class.strip_prefix("min-w-") {
-                smallvec![(
-                    &element_attrs::min_width as _,
-                    smallbox!(parse_size_val(class))
-                ),]
-            } else if let Some(class) = class.strip_prefix("min-h-") {
-                smallvec![(
-                    &element_attrs::min_height as _,
-                    smallbox!(parse_size_val(class))
-                ),]
-            } else if let Some(class) = class.strip_prefix("max-w-") {
-                smallvec![(
-                    &element_attrs::min_width as _,
-                    smallbox!(parse_size_val(class))
-                ),]
-            } else if let Some(class) = class.strip_prefix("max-h-") {
-                smallvec![(
-                    &element_attrs::max_height as _,
-                    smallbox!(parse_size_val(class))
-                ),]
-            } else if let Some(class) = class.strip_prefix("bg-") {
-                if let Some(color) = parse_color(class) {
-                    smallvec![(
-                        &element_attrs::background as _,
-                        smallbox!(UiTexture::Color(color))
-                    ),]
-                } else {
-                    default()
-                }
-            } else if let Some(class) = class.strip_prefix("text-") {
-                match class {
-                    "nowrap" => smallvec![(
-                        &element_attrs::text_linebreak as _,
-                        smallbox!(BreakLineOn::NoWrap)
-                    ),],
-                    "left" => smallvec![(
-                        &element_attrs::text_align as _,
-                        smallbox!(TextAlignment::Left)
-                    ),],
-                    "center" => smallvec![(
-                        &element_attrs::text_align as _,
-                        smallbox!(TextAlignment::Center)
-                    ),],
-                    "right" => smallvec![(
-                        &element_attrs::text_align as _,
-                        smallbox!(TextAlignment::Right)
-                    ),],
-                    _ => {
-                        if let Some(color) = parse_color(class) {
-                            smallvec![(&element_attrs::text_color as _, smallbox!(color)),]
-                        } else if let Ok(size) = class.parse::<f32>() {
-                            smallvec![(&element_attrs::font_size as _, smallbox!(size)),]
-                        } else {
-                            default()
-                        }
-                    }
-                }
-            } else if let Some(class) = class.strip_prefix("p-") {
-                let padding = parse_size_val(class);
-                smallvec![
-                    (&element_attrs::padding_left as _, smallbox!(padding)),
-                    (&element_attrs::padding_right as _, smallbox!(padding)),
-                    (&element_attrs::padding_top as _, smallbox!(padding)),
-                    (&element_attrs::padding_bottom as _, smallbox!(padding)),
-                ]
-            } else if let Some(class) = class.strip_prefix("py-") {
-                let padding = parse_size_val(class);
-                smallvec![
-                    (&element_attrs::padding_top as _, smallbox!(padding)),
-                    (&element_attrs::padding_bottom as _, smallbox!(padding)),
-                ]
-            } else if let Some(class) = class.strip_prefix("px-") {
-                let padding = parse_size_val(class);
-                smallvec![
-                    (&element_attrs::padding_left as _, smallbox!(padding)),
-                    (&element_attrs::padding_right as _, smallbox!(padding)),
-                ]
-            } else if let Some(class) = class.strip_prefix("pt-") {
-                let padding = parse_size_val(class);
-                smallvec![(&element_attrs::padding_top as _, smallbox!(padding)),]
-            } else if let Some(class) = class.strip_prefix("pb-") {
-                let padding = parse_size_val(class);
-                smallvec![(&element_attrs::padding_bottom as _, smallbox!(padding)),]
-            } else if let Some(class) = class.strip_prefix("pl-") {
-                let padding = parse_size_val(class);
-                smallvec![(&element_attrs::padding_left as _, smallbox!(padding)),]
-            } else if let Some(class) = class.strip_prefix("pr-") {
-                let padding = parse_size_val(class);
-                smallvec![(&element_attrs::padding_right as _, smallbox!(padding)),]
-            } else if let Some(class) = class.strip_prefix("m-") {
-                let margin = parse_size_val(class);
-                smallvec![
-                    (&element_attrs::margin_left as _, smallbox!(margin)),
-                    (&element_attrs::margin_right as _, smallbox!(margin)),
-                    (&element_attrs::margin_top as _, smallbox!(margin)),
-                    (&element_attrs::margin_bottom as _, smallbox!(margin)),
-                ]
-            } else if let Some(class) = class.strip_prefix("my-") {
-                let margin = parse_size_val(class);
-                smallvec![
-                    (&element_attrs::margin_top as _, smallbox!(margin)),
-                    (&element_attrs::margin_bottom as _, smallbox!(margin)),
-                ]
-            } else if let Some(class) = class.strip_prefix("mx-") {
-                let margin = parse_size_val(class);
-                smallvec![
-                    (&element_attrs::margin_left as _, smallbox!(margin)),
-                    (&element_attrs::margin_right as _, smallbox!(margin)),
-                ]
-            } else if let Some(class) = class.strip_prefix("mt-") {
-                let margin = parse_size_val(class);
-                smallvec![(&element_attrs::margin_top as _, smallbox!(margin)),]
-            } else if let Some(class) = class.strip_prefix("mb-") {
-                let margin = parse_size_val(class);
-                smallvec![(&element_attrs::margin_bottom as _, smallbox!(margin)),]
-            } else if let Some(class) = class.strip_prefix("ml-") {
-                let margin = parse_size_val(class);
-                smallvec![(&element_attrs::margin_left as _, smallbox!(margin)),]
-            } else if let Some(class) = class.strip_prefix("mr-") {
-                let margin = parse_size_val(class);
-                smallvec![(&element_attrs::margin_right as _, smallbox!(margin)),]
-            } else if let Some(class) = class.strip_prefix("border") {
-                if class == "" {
-                    smallvec![
-                        (&element_attrs::border_left as _, smallbox!(Val::Px(1.))),
-                        (&element_attrs::border_right as _, smallbox!(Val::Px(1.))),
-                        (&element_attrs::border_top as _, smallbox!(Val::Px(1.))),
-                        (&element_attrs::border_bottom as _, smallbox!(Val::Px(1.))),
-                    ]
-                } else if let Some(class) = class.strip_prefix("-") {
-                    if let Some(color) = parse_color(class) {
-                        smallvec![(
-                            &element_attrs::border_color as _,
-                            smallbox!(BorderColor(color))
-                        ),]
-                    } else if let Ok(size) = class.parse::<f32>() {
-                        let value = Val::Px(size as f32);
-                        smallvec![
-                            (&element_attrs::border_left as _, smallbox!(value)),
-                            (&element_attrs::border_right as _, smallbox!(value)),
-                            (&element_attrs::border_top as _, smallbox!(value)),
-                            (&element_attrs::border_bottom as _, smallbox!(value)),
-                        ]
-                    } else if let Some(class) = class.strip_prefix("l") {
-                        let Some(value) = parse_border_size(class) else {
-                            return default();
-                        };
-                        smallvec![(&element_attrs::border_left as _, smallbox!(value)),]
-                    } else if let Some(class) = class.strip_prefix("r") {
-                        let Some(value) = parse_border_size(class) else {
-                            return default();
-                        };
-                        smallvec![(&element_attrs::border_right as _, smallbox!(value)),]
-                    } else if let Some(class) = class.strip_prefix("t") {
-                        let Some(value) = parse_border_size(class) else {
-                            return default();
-                        };
-                        smallvec![(&element_attrs::border_top as _, smallbox!(value)),]
-                    } else if let Some(class) = class.strip_prefix("b") {
-                        let Some(value) = parse_border_size(class) else {
-                            return default();
-                        };
-                        smallvec![(&element_attrs::border_bottom as _, smallbox!(value)),]
-                    } else {
-                        default()
-                    }
-                } else {
-                    default()
-                }
-            } else if let Some(class) = class.strip_prefix("overflow-") {
-                let value = from_str(class).unwrap_or(OverflowAxis::Visible);
-                smallvec![
-                    (&element_attrs::overflow_x as _, smallbox!(value)),
-                    (&element_attrs::overflow_y as _, smallbox!(value))
-                ]
-            } else if let Some(class) = class.strip_prefix("overflow-x-") {
-                let value = from_str(class).unwrap_or(OverflowAxis::Visible);
-                smallvec![(&element_attrs::overflow_x as _, smallbox!(value)),]
-            } else if let Some(class) = class.strip_prefix("overflow-y-") {
-                let value = from_str(class).unwrap_or(OverflowAxis::Visible);
-                smallvec![(&element_attrs::overflow_y as _, smallbox!(value))]
-            } else {
-                default()
-            }
-        }
-    }
+pub fn parse_class<'a>(class: &'a str) -> TailwindClassItem {
+    parse_theme_class(TailwindTheme::builtin(), class)
 }
 
-pub fn parse_class<'a>(class: &'a str) -> TailwindClassItem {
-    if let Some(class) = class.strip_prefix("hover:") {
-        TailwindClassItem(parse_class_inner(class), Interaction::Hovered)
-    } else if let Some(class) = class.strip_prefix("active:") {
-        TailwindClassItem(parse_class_inner(class), Interaction::Pressed)
-    } else {
-        TailwindClassItem(parse_class_inner(class), default())
+pub fn parse_theme_class<'a>(theme: &TailwindTheme, class: &'a str) -> TailwindClassItem {
+    let mut states = ElementStates::NONE;
+    let mut class = class;
+    while let Some((variant, rest)) = class.split_once(':') {
+        let Some(state) = ElementStates::from_variant(variant)
+            .or_else(|| theme.color_scheme_state(variant))
+        else {
+            break;
+        };
+        states |= state;
+        class = rest;
     }
+    TailwindClassItem(parse_class_inner(theme, class), states)
 }
 
-fn parse_border_size(class: &str) -> Option<Val> {
-    if class == "" {
-        Some(Val::Px(1.0))
-    } else if let Some(class) = class.strip_prefix("-") {
-        Some(parse_size_val(class))
-    } else {
-        None
+/// Depth of named classes referring to other named classes, deeper ones are assumed to be cyclic
+const MAX_NAMED_CLASS_DEPTH: usize = 8;
+
+/// Calls `f` with each class of `classes`, the named classes of the theme are replaced by their
+/// utilities. Variants of a named class apply to all of them:
+/// `md:btn` → `md:p-2 md:hover:bg-gray-100`.
+pub fn expand_classes(theme: &TailwindTheme, classes: &str, f: &mut impl FnMut(&str)) {
+    expand_classes_with_variants(theme, classes, "", 0, f);
+}
+
+fn expand_classes_with_variants(
+    theme: &TailwindTheme,
+    classes: &str,
+    variants: &str,
+    depth: usize,
+    f: &mut impl FnMut(&str),
+) {
+    for class in classes.split_whitespace() {
+        // arbitrary values may contain `:`
+        let variants_end = class[..class.find('[').unwrap_or(class.len())]
+            .rfind(':')
+            .map_or(0, |n| n + 1);
+        let (class_variants, name) = class.split_at(variants_end);
+        match theme.class_utilities(name) {
+            Some(utilities) if depth < MAX_NAMED_CLASS_DEPTH => {
+                let variants = format!("{}{}", variants, class_variants);
+                expand_classes_with_variants(theme, utilities, &variants, depth + 1, f);
+            }
+            Some(_) => warn!("named class `{}` is nested too deep, it may refer to itself", name),
+            None if variants.is_empty() => f(class),
+            None => f(&format!("{}{}", variants, class)),
+        }
     }
 }
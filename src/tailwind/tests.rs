@@ -0,0 +1,515 @@
+use std::ops::Deref;
+
+use bevy::prelude::{Color, Font, Handle, Quat, Vec3, Visibility};
+use bevy::reflect::Reflect;
+use bevy::text::BreakLineOn;
+use bevy::ui::*;
+
+use crate::animation::{AnimationSpec, Easing, TransitionProperties, TransitionShorthand};
+use crate::entity_extra_data::ElementStates;
+use crate::prelude::TextAlignment;
+use crate::tailwind::{expand_classes, parse_theme_class, validate_theme_classes, TailwindTheme};
+use crate::UiTexture;
+
+fn values<T: Clone + 'static>(class: &str, attr: &str) -> Vec<T> {
+    values_with(&TailwindTheme::default(), class, attr)
+}
+
+fn values_with<T: Clone + 'static>(theme: &TailwindTheme, class: &str, attr: &str) -> Vec<T> {
+    parse_theme_class(theme, class)
+        .0
+        .iter()
+        .filter(|(prop, _)| prop.attr_name() == attr)
+        .map(|(_, value)| {
+            value
+                .deref()
+                .as_reflect()
+                .downcast_ref::<T>()
+                .unwrap_or_else(|| panic!("`{class}` sets `{attr}` with another type"))
+                .clone()
+        })
+        .collect()
+}
+
+fn value<T: Clone + 'static>(class: &str, attr: &str) -> T {
+    values(class, attr)
+        .pop()
+        .unwrap_or_else(|| panic!("`{class}` doesn't set `{attr}`"))
+}
+
+fn is_unknown(class: &str) -> bool {
+    parse_theme_class(&TailwindTheme::default(), class).0.is_empty()
+}
+
+#[test]
+fn visibility() {
+    assert_eq!(value::<Visibility>("visible", "visibility"), Visibility::Visible);
+    assert_eq!(value::<Visibility>("invisible", "visibility"), Visibility::Hidden);
+}
+
+#[test]
+fn display() {
+    assert_eq!(value::<Display>("flex", "display"), Display::Flex);
+    assert_eq!(value::<Display>("grid", "display"), Display::Grid);
+    assert_eq!(value::<Display>("hidden", "display"), Display::None);
+}
+
+#[test]
+fn position_type() {
+    assert_eq!(value::<PositionType>("relative", "position_type"), PositionType::Relative);
+    assert_eq!(value::<PositionType>("absolute", "position_type"), PositionType::Absolute);
+}
+
+#[test]
+fn direction() {
+    assert_eq!(value::<Direction>("dir-ltr", "direction"), Direction::LeftToRight);
+    assert_eq!(value::<Direction>("dir-rtl", "direction"), Direction::RightToLeft);
+    assert_eq!(value::<Direction>("dir-inherit", "direction"), Direction::Inherit);
+}
+
+#[test]
+fn z_index() {
+    assert!(matches!(value::<ZIndex>("z-10", "z_index"), ZIndex::Global(10)));
+    assert!(matches!(value::<ZIndex>("-z-10", "z_index"), ZIndex::Global(-10)));
+    assert!(matches!(value::<ZIndex>("z-[7]", "z_index"), ZIndex::Global(7)));
+    assert!(matches!(value::<ZIndex>("z-auto", "z_index"), ZIndex::Local(0)));
+}
+
+#[test]
+fn flex_direction() {
+    assert_eq!(value::<FlexDirection>("flex-row", "flex_direction"), FlexDirection::Row);
+    assert_eq!(
+        value::<FlexDirection>("flex-row-reverse", "flex_direction"),
+        FlexDirection::RowReverse
+    );
+    assert_eq!(value::<FlexDirection>("flex-col", "flex_direction"), FlexDirection::Column);
+    assert_eq!(
+        value::<FlexDirection>("flex-col-reverse", "flex_direction"),
+        FlexDirection::ColumnReverse
+    );
+    assert_eq!(value::<Display>("flex-col", "display"), Display::Flex);
+}
+
+#[test]
+fn flex_wrap() {
+    assert_eq!(value::<FlexWrap>("flex-wrap", "flex_wrap"), FlexWrap::Wrap);
+    assert_eq!(value::<FlexWrap>("flex-wrap-reverse", "flex_wrap"), FlexWrap::WrapReverse);
+    assert_eq!(value::<FlexWrap>("flex-nowrap", "flex_wrap"), FlexWrap::NoWrap);
+}
+
+#[test]
+fn flex() {
+    assert_eq!(value::<f32>("flex-1", "flex_grow"), 1.0);
+    assert_eq!(value::<f32>("flex-1", "flex_shrink"), 1.0);
+    assert_eq!(value::<Val>("flex-1", "flex_basis"), Val::Percent(0.0));
+    assert_eq!(value::<Val>("flex-auto", "flex_basis"), Val::Auto);
+    assert_eq!(value::<f32>("flex-initial", "flex_grow"), 0.0);
+    assert_eq!(value::<f32>("flex-none", "flex_shrink"), 0.0);
+}
+
+#[test]
+fn flex_grow() {
+    assert_eq!(value::<f32>("grow", "flex_grow"), 1.0);
+    assert_eq!(value::<f32>("grow-0", "flex_grow"), 0.0);
+    assert_eq!(value::<f32>("grow-[2.5]", "flex_grow"), 2.5);
+}
+
+#[test]
+fn flex_shrink() {
+    assert_eq!(value::<f32>("shrink", "flex_shrink"), 1.0);
+    assert_eq!(value::<f32>("shrink-0", "flex_shrink"), 0.0);
+}
+
+#[test]
+fn flex_basis() {
+    assert_eq!(value::<Val>("basis-4", "flex_basis"), Val::Px(16.0));
+    assert_eq!(value::<Val>("basis-1/2", "flex_basis"), Val::Percent(50.0));
+    assert_eq!(value::<Val>("basis-full", "flex_basis"), Val::Percent(100.0));
+    assert_eq!(value::<Val>("basis-auto", "flex_basis"), Val::Auto);
+}
+
+#[test]
+fn aspect_ratio() {
+    assert_eq!(value::<Option<f32>>("aspect-auto", "aspect_ratio"), None);
+    assert_eq!(value::<Option<f32>>("aspect-square", "aspect_ratio"), Some(1.0));
+    assert_eq!(value::<Option<f32>>("aspect-video", "aspect_ratio"), Some(16.0 / 9.0));
+    assert_eq!(value::<Option<f32>>("aspect-[4/3]", "aspect_ratio"), Some(4.0 / 3.0));
+    assert!(is_unknown("aspect-1/0"));
+}
+
+#[test]
+fn width() {
+    assert_eq!(value::<Val>("w-4", "width"), Val::Px(16.0));
+    assert_eq!(value::<Val>("w-px", "width"), Val::Px(1.0));
+    assert_eq!(value::<Val>("w-full", "width"), Val::Percent(100.0));
+    assert_eq!(value::<Val>("w-1/4", "width"), Val::Percent(25.0));
+    assert_eq!(value::<Val>("w-50%", "width"), Val::Percent(50.0));
+    assert_eq!(value::<Val>("w-10px", "width"), Val::Px(10.0));
+    assert_eq!(value::<Val>("w-[37px]", "width"), Val::Px(37.0));
+    assert_eq!(value::<Val>("w-screen", "width"), Val::Vw(100.0));
+    assert_eq!(value::<Val>("w-auto", "width"), Val::Auto);
+    assert!(is_unknown("w-wide"));
+}
+
+#[test]
+fn height() {
+    assert_eq!(value::<Val>("h-2", "height"), Val::Px(8.0));
+    assert_eq!(value::<Val>("h-screen", "height"), Val::Vh(100.0));
+    assert_eq!(value::<Val>("h-[10vh]", "height"), Val::Vh(10.0));
+}
+
+#[test]
+fn size() {
+    assert_eq!(value::<Val>("size-8", "width"), Val::Px(32.0));
+    assert_eq!(value::<Val>("size-8", "height"), Val::Px(32.0));
+}
+
+#[test]
+fn min_width() {
+    assert_eq!(value::<Val>("min-w-10", "min_width"), Val::Px(40.0));
+}
+
+#[test]
+fn min_height() {
+    assert_eq!(value::<Val>("min-h-screen", "min_height"), Val::Vh(100.0));
+}
+
+#[test]
+fn max_width() {
+    assert_eq!(value::<Val>("max-w-20", "max_width"), Val::Px(80.0));
+    assert!(values::<Val>("max-w-20", "min_width").is_empty());
+}
+
+#[test]
+fn max_height() {
+    assert_eq!(value::<Val>("max-h-1/2", "max_height"), Val::Percent(50.0));
+}
+
+#[test]
+fn gap() {
+    assert_eq!(value::<Val>("gap-2", "column_gap"), Val::Px(8.0));
+    assert_eq!(value::<Val>("gap-2", "row_gap"), Val::Px(8.0));
+    assert_eq!(value::<Val>("gap-x-3", "column_gap"), Val::Px(12.0));
+    assert!(values::<Val>("gap-x-3", "row_gap").is_empty());
+    assert_eq!(value::<Val>("gap-y-1", "row_gap"), Val::Px(4.0));
+    assert!(values::<Val>("gap-y-1", "column_gap").is_empty());
+}
+
+#[test]
+fn padding() {
+    for attr in ["padding_left", "padding_right", "padding_top", "padding_bottom"] {
+        assert_eq!(value::<Val>("p-2", attr), Val::Px(8.0));
+    }
+    assert_eq!(value::<Val>("px-1", "padding_left"), Val::Px(4.0));
+    assert_eq!(value::<Val>("px-1", "padding_right"), Val::Px(4.0));
+    assert_eq!(value::<Val>("py-1", "padding_top"), Val::Px(4.0));
+    assert_eq!(value::<Val>("py-1", "padding_bottom"), Val::Px(4.0));
+    assert_eq!(value::<Val>("pt-1", "padding_top"), Val::Px(4.0));
+    assert_eq!(value::<Val>("pr-1", "padding_right"), Val::Px(4.0));
+    assert_eq!(value::<Val>("pb-1", "padding_bottom"), Val::Px(4.0));
+    assert_eq!(value::<Val>("pl-1", "padding_left"), Val::Px(4.0));
+    assert!(is_unknown("-p-1"));
+}
+
+#[test]
+fn margin() {
+    for attr in ["margin_left", "margin_right", "margin_top", "margin_bottom"] {
+        assert_eq!(value::<Val>("m-auto", attr), Val::Auto);
+    }
+    assert_eq!(value::<Val>("mx-2", "margin_left"), Val::Px(8.0));
+    assert_eq!(value::<Val>("my-2", "margin_bottom"), Val::Px(8.0));
+    assert_eq!(value::<Val>("mt-2", "margin_top"), Val::Px(8.0));
+    assert_eq!(value::<Val>("mr-2", "margin_right"), Val::Px(8.0));
+    assert_eq!(value::<Val>("mb-2", "margin_bottom"), Val::Px(8.0));
+    assert_eq!(value::<Val>("ml-2", "margin_left"), Val::Px(8.0));
+    assert_eq!(value::<Val>("-mt-2", "margin_top"), Val::Px(-8.0));
+    assert_eq!(value::<Val>("-mx-[10%]", "margin_right"), Val::Percent(-10.0));
+}
+
+#[test]
+fn inset() {
+    for attr in ["left", "right", "top", "bottom"] {
+        assert_eq!(value::<Val>("inset-0", attr), Val::Px(0.0));
+    }
+    assert_eq!(value::<Val>("inset-x-1", "left"), Val::Px(4.0));
+    assert!(values::<Val>("inset-x-1", "top").is_empty());
+    assert_eq!(value::<Val>("inset-y-1", "bottom"), Val::Px(4.0));
+    assert_eq!(value::<Val>("left-2", "left"), Val::Px(8.0));
+    assert_eq!(value::<Val>("right-2", "right"), Val::Px(8.0));
+    assert_eq!(value::<Val>("top-1/2", "top"), Val::Percent(50.0));
+    assert_eq!(value::<Val>("-bottom-2", "bottom"), Val::Px(-8.0));
+}
+
+#[test]
+fn translate() {
+    assert_eq!(value::<Val>("translate-x-4", "translate_x"), Val::Px(16.0));
+    assert_eq!(value::<Val>("-translate-y-1/2", "translate_y"), Val::Percent(-50.0));
+    // `top-1/2 -translate-y-1/2` centers, the translation doesn't replace the position
+    assert!(values::<Val>("-translate-y-1/2", "top").is_empty());
+}
+
+#[test]
+fn justify_content() {
+    assert_eq!(
+        value::<JustifyContent>("justify-between", "justify_content"),
+        JustifyContent::SpaceBetween
+    );
+    assert_eq!(
+        value::<JustifyContent>("justify-normal", "justify_content"),
+        JustifyContent::Default
+    );
+    assert!(is_unknown("justify-sideways"));
+}
+
+#[test]
+fn justify_items() {
+    assert_eq!(
+        value::<JustifyItems>("justify-items-center", "justify_items"),
+        JustifyItems::Center
+    );
+    assert!(values::<JustifyContent>("justify-items-center", "justify_content").is_empty());
+}
+
+#[test]
+fn justify_self() {
+    assert_eq!(value::<JustifySelf>("justify-self-end", "justify_self"), JustifySelf::End);
+    assert_eq!(value::<JustifySelf>("justify-self-auto", "justify_self"), JustifySelf::Auto);
+}
+
+#[test]
+fn align_items() {
+    assert_eq!(value::<AlignItems>("items-start", "align_items"), AlignItems::FlexStart);
+    assert_eq!(value::<AlignItems>("items-center", "align_items"), AlignItems::Center);
+}
+
+#[test]
+fn align_self() {
+    assert_eq!(value::<AlignSelf>("self-end", "align_self"), AlignSelf::FlexEnd);
+    assert_eq!(value::<AlignSelf>("self-stretch", "align_self"), AlignSelf::Stretch);
+}
+
+#[test]
+fn align_content() {
+    assert_eq!(
+        value::<AlignContent>("content-around", "align_content"),
+        AlignContent::SpaceAround
+    );
+    assert_eq!(value::<AlignContent>("content-start", "align_content"), AlignContent::FlexStart);
+}
+
+#[test]
+fn grid_template() {
+    assert_eq!(
+        value::<Vec<RepeatedGridTrack>>("grid-cols-3", "grid_template_columns"),
+        vec![RepeatedGridTrack::flex(3, 1.0)]
+    );
+    assert_eq!(
+        value::<Vec<RepeatedGridTrack>>("grid-rows-none", "grid_template_rows"),
+        vec![]
+    );
+    assert!(is_unknown("grid-cols-0"));
+}
+
+#[test]
+fn grid_auto_flow() {
+    assert_eq!(value::<GridAutoFlow>("grid-flow-col", "grid_auto_flow"), GridAutoFlow::Column);
+    assert_eq!(
+        value::<GridAutoFlow>("grid-flow-dense", "grid_auto_flow"),
+        GridAutoFlow::RowDense
+    );
+}
+
+#[test]
+fn grid_auto_tracks() {
+    assert_eq!(
+        value::<Vec<GridTrack>>("auto-cols-fr", "grid_auto_columns"),
+        vec![GridTrack::fr(1.0)]
+    );
+    assert_eq!(
+        value::<Vec<GridTrack>>("auto-rows-min", "grid_auto_rows"),
+        vec![GridTrack::min_content()]
+    );
+}
+
+#[test]
+fn grid_placement() {
+    assert_eq!(value::<GridPlacement>("col-span-2", "grid_column"), GridPlacement::span(2));
+    assert_eq!(value::<i64>("row-start-3", "grid_row_start"), 3);
+    assert_eq!(value::<i64>("col-end-4", "grid_column_end"), 4);
+    assert!(is_unknown("col-start-0"));
+    assert!(is_unknown("col-span-0"));
+}
+
+#[test]
+fn overflow() {
+    assert_eq!(value::<OverflowAxis>("overflow-clip", "overflow_x"), OverflowAxis::Clip);
+    assert_eq!(value::<OverflowAxis>("overflow-clip", "overflow_y"), OverflowAxis::Clip);
+    assert_eq!(value::<OverflowAxis>("overflow-x-clip", "overflow_x"), OverflowAxis::Clip);
+    assert!(values::<OverflowAxis>("overflow-x-clip", "overflow_y").is_empty());
+    assert_eq!(value::<OverflowAxis>("overflow-y-clip", "overflow_y"), OverflowAxis::Clip);
+    assert!(is_unknown("overflow-sideways"));
+}
+
+#[test]
+fn background() {
+    assert!(matches!(
+        value::<UiTexture>("bg-[#ff0000]", "background"),
+        UiTexture::Color(color) if color == Color::rgb(1.0, 0.0, 0.0)
+    ));
+    assert!(is_unknown("bg-nope-500"));
+}
+
+#[test]
+fn border_width() {
+    for attr in ["border_left", "border_right", "border_top", "border_bottom"] {
+        assert_eq!(value::<Val>("border", attr), Val::Px(1.0));
+        assert_eq!(value::<Val>("border-2", attr), Val::Px(2.0));
+    }
+    assert_eq!(value::<Val>("border-[3px]", "border_top"), Val::Px(3.0));
+    assert_eq!(value::<Val>("border-x", "border_left"), Val::Px(1.0));
+    assert_eq!(value::<Val>("border-x-2", "border_right"), Val::Px(2.0));
+    assert!(values::<Val>("border-x-2", "border_top").is_empty());
+    assert_eq!(value::<Val>("border-y-2", "border_bottom"), Val::Px(2.0));
+    assert_eq!(value::<Val>("border-l", "border_left"), Val::Px(1.0));
+    assert_eq!(value::<Val>("border-r-4", "border_right"), Val::Px(4.0));
+    assert_eq!(value::<Val>("border-t-0", "border_top"), Val::Px(0.0));
+    assert_eq!(value::<Val>("border-b-[2px]", "border_bottom"), Val::Px(2.0));
+}
+
+#[test]
+fn border_color() {
+    let color = value::<BorderColor>("border-[#00ff00]", "border_color");
+    assert_eq!(color.0, Color::rgb(0.0, 1.0, 0.0));
+}
+
+#[test]
+fn text_color() {
+    assert_eq!(value::<Color>("text-white", "text_color"), Color::WHITE);
+}
+
+#[test]
+fn font_size() {
+    assert_eq!(value::<f32>("text-lg", "font_size"), 18.0);
+    assert_eq!(value::<f32>("text-[22px]", "font_size"), 22.0);
+    assert_eq!(value::<f32>("text-15", "font_size"), 15.0);
+}
+
+#[test]
+fn font() {
+    let theme = TailwindTheme::default().with_font("mono", "fonts/FiraMono-Medium.ttf");
+    assert_eq!(values_with::<Handle<Font>>(&theme, "font-mono", "font").len(), 1);
+    assert!(is_unknown("font-mono"));
+}
+
+#[test]
+fn text_align() {
+    assert_eq!(value::<TextAlignment>("text-left", "text_align"), TextAlignment::Left);
+    assert_eq!(value::<TextAlignment>("text-center", "text_align"), TextAlignment::Center);
+    assert_eq!(value::<TextAlignment>("text-right", "text_align"), TextAlignment::Right);
+}
+
+#[test]
+fn text_linebreak() {
+    let linebreak = |class| value::<BreakLineOn>(class, "text_linebreak");
+    assert_eq!(linebreak("text-nowrap"), BreakLineOn::NoWrap);
+    assert_eq!(linebreak("text-wrap"), BreakLineOn::WordBoundary);
+    assert_eq!(linebreak("whitespace-nowrap"), BreakLineOn::NoWrap);
+    assert_eq!(linebreak("whitespace-normal"), BreakLineOn::WordBoundary);
+    assert_eq!(linebreak("break-normal"), BreakLineOn::WordBoundary);
+    assert_eq!(linebreak("break-words"), BreakLineOn::WordBoundary);
+    assert_eq!(linebreak("break-all"), BreakLineOn::AnyCharacter);
+}
+
+#[test]
+fn rotation() {
+    let rotation = value::<Quat>("rotate-90", "rotation");
+    assert!(rotation.abs_diff_eq(Quat::from_rotation_z(90f32.to_radians()), 1e-6));
+    let rotation = value::<Quat>("-rotate-90", "rotation");
+    assert!(rotation.abs_diff_eq(Quat::from_rotation_z(-90f32.to_radians()), 1e-6));
+    let rotation = value::<Quat>("rotate-[0.5turn]", "rotation");
+    assert!(rotation.abs_diff_eq(Quat::from_rotation_z(std::f32::consts::PI), 1e-6));
+}
+
+#[test]
+fn scale() {
+    assert_eq!(value::<Vec3>("scale-150", "scale"), Vec3::new(1.5, 1.5, 1.0));
+    assert_eq!(value::<Vec3>("scale-[0.8]", "scale"), Vec3::new(0.8, 0.8, 1.0));
+    assert!(is_unknown("-scale-50"));
+}
+
+#[test]
+fn transition() {
+    let properties = |class| value::<TransitionShorthand>(class, "transition").properties;
+    assert_eq!(
+        properties("transition"),
+        Some(TransitionProperties::COLORS | TransitionProperties::TRANSFORM)
+    );
+    assert_eq!(properties("transition-all"), Some(TransitionProperties::ALL));
+    assert_eq!(properties("transition-colors"), Some(TransitionProperties::COLORS));
+    assert_eq!(properties("transition-transform"), Some(TransitionProperties::TRANSFORM));
+    assert_eq!(properties("transition-none"), Some(TransitionProperties::NONE));
+}
+
+#[test]
+fn transition_duration() {
+    assert_eq!(value::<f32>("duration-300", "transition_duration"), 300.0);
+    assert_eq!(value::<f32>("duration-[0.5s]", "transition_duration"), 500.0);
+}
+
+#[test]
+fn transition_delay() {
+    assert_eq!(value::<f32>("delay-75", "transition_delay"), 75.0);
+}
+
+#[test]
+fn transition_timing() {
+    assert_eq!(value::<Easing>("ease-linear", "transition_timing"), Easing::Linear);
+    assert_eq!(value::<Easing>("ease-in", "transition_timing"), Easing::EaseIn);
+    assert_eq!(value::<Easing>("ease-out", "transition_timing"), Easing::EaseOut);
+    assert_eq!(value::<Easing>("ease-in-out", "transition_timing"), Easing::EaseInOut);
+}
+
+#[test]
+fn animation() {
+    assert_eq!(value::<AnimationSpec>("animate-none", "animation").name, "");
+    assert_eq!(value::<AnimationSpec>("animate-pulse", "animation").name, "pulse");
+    let spec = value::<AnimationSpec>("animate-[flash_300ms_ease-out]", "animation");
+    assert_eq!(spec.name, "flash");
+    assert!((spec.duration - 0.3).abs() < 1e-6);
+    let theme = TailwindTheme::default().with_animation("spin", "spin 2s linear infinite");
+    let spec = values_with::<AnimationSpec>(&theme, "animate-spin", "animation").remove(0);
+    assert_eq!(spec.duration, 2.0);
+    assert_eq!(spec.easing, Easing::Linear);
+}
+
+#[test]
+fn variants() {
+    let item = parse_theme_class(&TailwindTheme::default(), "hover:md:w-4");
+    assert_eq!(item.1, ElementStates::HOVER | ElementStates::MD);
+    assert_eq!(item.0.len(), 1);
+}
+
+#[test]
+fn unknown_classes() {
+    let theme = TailwindTheme::default();
+    assert_eq!(validate_theme_classes(&theme, "group p-2 grow hover:bg-gray-100"), Ok(()));
+    assert_eq!(
+        validate_theme_classes(&theme, "p-2 flex-grow-1 hovr:bg-gray-100 w-"),
+        Err(vec!["flex-grow-1".to_string(), "hovr:bg-gray-100".to_string(), "w-".to_string()])
+    );
+}
+
+#[test]
+fn named_classes() {
+    let theme = TailwindTheme::default()
+        .with_class("btn", "p-2 hover:bg-gray-100")
+        .with_class("btn-wide", "btn px-8")
+        .with_class("loop", "loop");
+    let mut classes = vec![];
+    expand_classes(&theme, "btn-wide md:btn w-[1px] loop", &mut |n| classes.push(n.to_string()));
+    assert_eq!(
+        classes,
+        ["p-2", "hover:bg-gray-100", "px-8", "md:p-2", "md:hover:bg-gray-100", "w-[1px]"]
+    );
+    assert_eq!(validate_theme_classes(&theme, "btn-wide"), Ok(()));
+}
@@ -0,0 +1,291 @@
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::{TypePath, TypeUuid};
+use bevy::utils::{BoxedFuture, HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::entity_extra_data::ElementStates;
+use crate::tailwind::{insert_default_colors, Colors, TailwindClassCache};
+use crate::{ReapplyClassesCommand, TemplateWorld};
+
+/// Values the tailwind classes resolve against.
+///
+/// Loaded from `*.theme.ron` / `*.theme.json` assets, fields missing in the file keep their default.
+/// Replacing the resource re-applies the classes of every element, the template world gets a copy.
+#[derive(Resource, TypeUuid, TypePath, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[uuid = "8d6e3c1a-3f4b-4a57-9a43-5e1f1c2b7d10"]
+#[serde(default)]
+pub struct TailwindTheme {
+    /// `bg-red-500`, replaces the default palette when given
+    pub colors: Colors,
+    /// `bg-brand`, `text-brand/50`
+    pub custom_colors: HashMap<String, [u8; 4]>,
+    /// pixels of one spacing step, `p-2` is `2 * spacing_unit`
+    pub spacing_unit: f32,
+    /// named spacing in pixels, `w-px`, `gap-gutter`
+    pub spacing: HashMap<String, f32>,
+    /// `text-sm`, `text-2xl`
+    pub font_sizes: HashMap<String, f32>,
+    /// min window width of the `sm:`, `md:` ... variants
+    pub breakpoints: HashMap<String, f32>,
+    /// names of the color schemes usable as variants, `orc:bg-green-700`
+    pub color_schemes: Vec<String>,
+    /// asset paths of `font-mono`, `font-title` ..., loaded when the theme is applied
+    pub fonts: HashMap<String, String>,
+    /// `animation` shorthands of `animate-spin` ..., other names play the keyframes of that name
+    pub animations: HashMap<String, String>,
+    /// named classes replaced by their utilities before parsing,
+    /// `btn-primary` → `"px-4 py-2 bg-blue-500 hover:bg-blue-600"`
+    pub classes: HashMap<String, String>,
+}
+
+impl Default for TailwindTheme {
+    fn default() -> Self {
+        let mut colors = Colors::new();
+        insert_default_colors(&mut colors);
+        Self {
+            colors,
+            custom_colors: default(),
+            spacing_unit: 4.0,
+            spacing: [("px", 1.0)]
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+            font_sizes: [
+                ("xs", 12.0),
+                ("sm", 14.0),
+                ("base", 16.0),
+                ("lg", 18.0),
+                ("xl", 20.0),
+                ("2xl", 24.0),
+                ("3xl", 30.0),
+                ("4xl", 36.0),
+                ("5xl", 48.0),
+                ("6xl", 60.0),
+                ("7xl", 72.0),
+                ("8xl", 96.0),
+                ("9xl", 128.0),
+            ]
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+            breakpoints: [
+                ("sm", 640.0),
+                ("md", 768.0),
+                ("lg", 1024.0),
+                ("xl", 1280.0),
+                ("2xl", 1536.0),
+            ]
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+            color_schemes: vec![],
+            fonts: default(),
+            animations: default(),
+            classes: default(),
+        }
+    }
+}
+
+impl TailwindTheme {
+    /// The default theme, attr values parsed from strings resolve against it
+    pub fn builtin() -> &'static TailwindTheme {
+        static BUILTIN: OnceLock<TailwindTheme> = OnceLock::new();
+        BUILTIN.get_or_init(default)
+    }
+
+    pub fn with_custom_color(mut self, name: impl Into<String>, color: Color) -> Self {
+        self.custom_colors.insert(name.into(), color.as_rgba_u8());
+        self
+    }
+
+    /// `500` of palette `red`, or a custom color when `variant` is `None`
+    pub fn color(&self, name: &str, variant: Option<&str>) -> Option<[u8; 4]> {
+        match variant {
+            Some(variant) => self
+                .colors
+                .get(name)
+                .and_then(|variants| variants.get(variant))
+                .copied(),
+            None => self.custom_colors.get(name).copied().or_else(|| {
+                self.colors
+                    .get(name)
+                    .and_then(|variants| variants.values().next())
+                    .copied()
+            }),
+        }
+    }
+
+    pub fn spacing(&self, name: &str) -> Option<f32> {
+        self.spacing.get(name).copied()
+    }
+
+    pub fn font_size(&self, name: &str) -> Option<f32> {
+        self.font_sizes.get(name).copied()
+    }
+
+    pub fn breakpoint(&self, name: &str) -> Option<f32> {
+        self.breakpoints.get(name).copied()
+    }
+
+    pub fn with_color_scheme(mut self, name: impl Into<String>) -> Self {
+        self.color_schemes.push(name.into());
+        self
+    }
+
+    /// `font-{name}` loads the font at `path`
+    pub fn with_font(mut self, name: impl Into<String>, path: impl Into<String>) -> Self {
+        self.fonts.insert(name.into(), path.into());
+        self
+    }
+
+    /// `animate-{name}` plays the `animation` shorthand, `"spin 1s linear infinite"`
+    pub fn with_animation(mut self, name: impl Into<String>, shorthand: impl Into<String>) -> Self {
+        self.animations.insert(name.into(), shorthand.into());
+        self
+    }
+
+    /// `name` in a `class` attr is replaced by `utilities`, they may have variants and be named classes
+    pub fn with_class(mut self, name: impl Into<String>, utilities: impl Into<String>) -> Self {
+        self.classes.insert(name.into(), utilities.into());
+        self
+    }
+
+    pub fn class_utilities(&self, name: &str) -> Option<&str> {
+        self.classes.get(name).map(String::as_str)
+    }
+
+    /// Variant state of a named color scheme, `None` past [`ElementStates::MAX_COLOR_SCHEMES`]
+    pub fn color_scheme_state(&self, name: &str) -> Option<ElementStates> {
+        let index = self.color_schemes.iter().position(|n| n == name)?;
+        ElementStates::color_scheme(index)
+    }
+}
+
+/// Active color scheme, enables the `dark:` variant and the variant of the named scheme
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq)]
+#[reflect(Resource, Default)]
+pub struct UiColorScheme {
+    pub dark: bool,
+    /// one of [`TailwindTheme::color_schemes`]
+    pub name: Option<String>,
+}
+
+impl UiColorScheme {
+    pub fn states(&self, theme: &TailwindTheme) -> ElementStates {
+        let mut states = ElementStates::NONE;
+        states.set(ElementStates::DARK, self.dark);
+        if let Some(state) = self
+            .name
+            .as_deref()
+            .and_then(|name| theme.color_scheme_state(name))
+        {
+            states |= state;
+        }
+        states
+    }
+}
+
+#[derive(Default)]
+pub struct TailwindThemeLoader;
+
+impl AssetLoader for TailwindThemeLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let is_json = load_context
+                .path()
+                .extension()
+                .is_some_and(|n| n == "json");
+            let theme: TailwindTheme = if is_json {
+                serde_json::from_slice(bytes)?
+            } else {
+                ron::de::from_bytes(bytes)?
+            };
+            load_context.set_default_asset(LoadedAsset::new(theme));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron", "theme.json"]
+    }
+}
+
+/// Theme asset copied into the [`TailwindTheme`] resource whenever it is (re)loaded
+#[derive(Resource, Clone, Debug, Default, Deref)]
+pub struct TailwindThemeHandle(pub Handle<TailwindTheme>);
+
+pub fn sync_tailwind_theme_asset(
+    mut events: EventReader<AssetEvent<TailwindTheme>>,
+    handle: Option<Res<TailwindThemeHandle>>,
+    themes: Res<Assets<TailwindTheme>>,
+    mut theme: ResMut<TailwindTheme>,
+) {
+    let Some(handle) = handle else {
+        events.clear();
+        return;
+    };
+    let mut changed = handle.is_changed();
+    for event in events.iter() {
+        if let AssetEvent::Created { handle: changed_handle }
+        | AssetEvent::Modified { handle: changed_handle } = event
+        {
+            changed |= *changed_handle == handle.0;
+        }
+    }
+    if !changed {
+        return;
+    }
+    if let Some(loaded) = themes.get(&handle.0) {
+        if *theme != *loaded {
+            *theme = loaded.clone();
+        }
+    }
+}
+
+pub fn apply_tailwind_theme(
+    mut commands: Commands,
+    theme: Res<TailwindTheme>,
+    mut template_world: ResMut<TemplateWorld>,
+    class_cache: Res<TailwindClassCache>,
+    asset_server: Option<Res<AssetServer>>,
+    mut fonts: Local<Vec<Handle<Font>>>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+    let ignored = theme.color_schemes.get(ElementStates::MAX_COLOR_SCHEMES..);
+    if let Some(ignored) = ignored.filter(|n| !n.is_empty()) {
+        warn!(
+            "color schemes {:?} are ignored, at most {} are supported",
+            ignored,
+            ElementStates::MAX_COLOR_SCHEMES
+        );
+    }
+    // `font-*` classes hold weak handles, these keep the fonts loaded
+    if let Some(asset_server) = asset_server {
+        *fonts = theme.fonts.values().map(|path| asset_server.load(path.as_str())).collect();
+    }
+    template_world.insert_resource(theme.clone());
+    class_cache.clear();
+    commands.add(ReapplyClassesCommand(None));
+}
+
+pub trait TailwindThemeAppExt {
+    /// Loads the theme asset, it is applied again on every hot reload
+    fn load_tailwind_theme(&mut self, path: &str) -> &mut Self;
+}
+
+impl TailwindThemeAppExt for App {
+    fn load_tailwind_theme(&mut self, path: &str) -> &mut Self {
+        let handle = self.world.resource::<AssetServer>().load(path);
+        self.insert_resource(TailwindThemeHandle(handle))
+    }
+}
@@ -0,0 +1,602 @@
+//! Utility classes by name and by prefix, each maps to attrs of `CommonAttrs`.
+//!
+//! Attrs that aren't styles (`class`, `name`, `components`, `draggable`, `disabled`, `checked`,
+//! `animation_handle`) have no utility. `leading-*` has no attr to map to, bevy text has no line
+//! height yet.
+
+use std::ops::DerefMut;
+
+use bevy::asset::{AssetPath, HandleId};
+use bevy::prelude::{Font, Handle, Quat, Vec3, Visibility};
+use bevy::reflect::Reflect;
+use bevy::text::BreakLineOn;
+use bevy::ui::*;
+use smallvec::{smallvec, SmallVec};
+
+use crate::animation::{
+    parse_duration_ms, AnimationSpec, Easing, TransitionProperties, TransitionShorthand,
+};
+use crate::element_core::{AttrValue, ElementAttr};
+use crate::prelude::TextAlignment;
+use crate::smallbox::S1;
+use crate::tailwind::{parse_theme_color, TailwindTheme};
+use crate::{element_attrs, from_str, smallbox, ElementAttrUntyped, SmallBox, UiTexture};
+
+pub type ClassAttrs = SmallVec<[(&'static dyn ElementAttrUntyped, SmallBox<dyn AttrValue, S1>); 4]>;
+
+/// `class_attrs![width: Val::Px(10.), height: Val::Auto]`, values must be of the attr value type
+macro_rules! class_attrs {
+    ($($attr:ident: $value:expr),* $(,)?) => {
+        smallvec![$(
+            (
+                &element_attrs::$attr as &'static dyn ElementAttrUntyped,
+                {
+                    let value: <element_attrs::$attr as ElementAttr>::Value = $value;
+                    smallbox!(value)
+                },
+            )
+        ),*]
+    };
+}
+
+type StaticUtility = (&'static str, fn() -> ClassAttrs);
+type ValueUtility = (&'static str, fn(&TailwindTheme, &str) -> Option<ClassAttrs>);
+
+/// Classes matched by their full name
+const STATIC_UTILITIES: &[StaticUtility] = &[
+    ("visible", || class_attrs![visibility: Visibility::Visible]),
+    ("invisible", || class_attrs![visibility: Visibility::Hidden]),
+    ("flex", || class_attrs![display: Display::Flex]),
+    ("grid", || class_attrs![display: Display::Grid]),
+    ("hidden", || class_attrs![display: Display::None]),
+    ("relative", || class_attrs![position_type: PositionType::Relative]),
+    ("absolute", || class_attrs![position_type: PositionType::Absolute]),
+    ("dir-ltr", || class_attrs![direction: Direction::LeftToRight]),
+    ("dir-rtl", || class_attrs![direction: Direction::RightToLeft]),
+    ("dir-inherit", || class_attrs![direction: Direction::Inherit]),
+    ("flex-row", || {
+        class_attrs![display: Display::Flex, flex_direction: FlexDirection::Row]
+    }),
+    ("flex-row-reverse", || {
+        class_attrs![display: Display::Flex, flex_direction: FlexDirection::RowReverse]
+    }),
+    ("flex-col", || {
+        class_attrs![display: Display::Flex, flex_direction: FlexDirection::Column]
+    }),
+    ("flex-col-reverse", || {
+        class_attrs![display: Display::Flex, flex_direction: FlexDirection::ColumnReverse]
+    }),
+    ("flex-wrap", || class_attrs![flex_wrap: FlexWrap::Wrap]),
+    ("flex-wrap-reverse", || class_attrs![flex_wrap: FlexWrap::WrapReverse]),
+    ("flex-nowrap", || class_attrs![flex_wrap: FlexWrap::NoWrap]),
+    ("flex-1", || {
+        class_attrs![flex_grow: 1.0, flex_shrink: 1.0, flex_basis: Val::Percent(0.0)]
+    }),
+    ("flex-auto", || {
+        class_attrs![flex_grow: 1.0, flex_shrink: 1.0, flex_basis: Val::Auto]
+    }),
+    ("flex-initial", || {
+        class_attrs![flex_grow: 0.0, flex_shrink: 1.0, flex_basis: Val::Auto]
+    }),
+    ("flex-none", || {
+        class_attrs![flex_grow: 0.0, flex_shrink: 0.0, flex_basis: Val::Auto]
+    }),
+    ("grow", || class_attrs![flex_grow: 1.0]),
+    ("shrink", || class_attrs![flex_shrink: 1.0]),
+    ("aspect-auto", || class_attrs![aspect_ratio: None]),
+    ("aspect-square", || class_attrs![aspect_ratio: Some(1.0)]),
+    ("aspect-video", || class_attrs![aspect_ratio: Some(16.0 / 9.0)]),
+    ("grid-cols-none", || class_attrs![grid_template_columns: vec![]]),
+    ("grid-rows-none", || class_attrs![grid_template_rows: vec![]]),
+    ("grid-flow-row", || class_attrs![grid_auto_flow: GridAutoFlow::Row]),
+    ("grid-flow-col", || class_attrs![grid_auto_flow: GridAutoFlow::Column]),
+    ("grid-flow-dense", || class_attrs![grid_auto_flow: GridAutoFlow::RowDense]),
+    ("grid-flow-row-dense", || class_attrs![grid_auto_flow: GridAutoFlow::RowDense]),
+    ("grid-flow-col-dense", || class_attrs![grid_auto_flow: GridAutoFlow::ColumnDense]),
+    ("border", || border_widths(Val::Px(1.0))),
+    ("border-x", || class_attrs![border_left: Val::Px(1.0), border_right: Val::Px(1.0)]),
+    ("border-y", || class_attrs![border_top: Val::Px(1.0), border_bottom: Val::Px(1.0)]),
+    ("border-l", || class_attrs![border_left: Val::Px(1.0)]),
+    ("border-r", || class_attrs![border_right: Val::Px(1.0)]),
+    ("border-t", || class_attrs![border_top: Val::Px(1.0)]),
+    ("border-b", || class_attrs![border_bottom: Val::Px(1.0)]),
+    ("text-left", || class_attrs![text_align: TextAlignment::Left]),
+    ("text-center", || class_attrs![text_align: TextAlignment::Center]),
+    ("text-right", || class_attrs![text_align: TextAlignment::Right]),
+    ("text-wrap", || class_attrs![text_linebreak: BreakLineOn::WordBoundary]),
+    ("text-nowrap", || class_attrs![text_linebreak: BreakLineOn::NoWrap]),
+    ("whitespace-normal", || class_attrs![text_linebreak: BreakLineOn::WordBoundary]),
+    ("whitespace-nowrap", || class_attrs![text_linebreak: BreakLineOn::NoWrap]),
+    ("break-normal", || class_attrs![text_linebreak: BreakLineOn::WordBoundary]),
+    ("break-words", || class_attrs![text_linebreak: BreakLineOn::WordBoundary]),
+    ("break-all", || class_attrs![text_linebreak: BreakLineOn::AnyCharacter]),
+    ("transition", || {
+        transition_properties(TransitionProperties::COLORS | TransitionProperties::TRANSFORM)
+    }),
+    ("transition-all", || transition_properties(TransitionProperties::ALL)),
+    ("transition-colors", || transition_properties(TransitionProperties::COLORS)),
+    ("transition-transform", || transition_properties(TransitionProperties::TRANSFORM)),
+    ("transition-none", || transition_properties(TransitionProperties::NONE)),
+    ("ease-linear", || class_attrs![transition_timing: Easing::Linear]),
+    ("ease-in", || class_attrs![transition_timing: Easing::EaseIn]),
+    ("ease-out", || class_attrs![transition_timing: Easing::EaseOut]),
+    ("ease-in-out", || class_attrs![transition_timing: Easing::EaseInOut]),
+    ("animate-none", || class_attrs![animation: AnimationSpec::default()]),
+];
+
+/// Classes with a value after the prefix, the longest matching prefix is used
+const VALUE_UTILITIES: &[ValueUtility] = &[
+    ("z-", |_, value| {
+        let z_index = match value {
+            "auto" => ZIndex::Local(0),
+            value => ZIndex::Global(arbitrary_value(value).unwrap_or(value).parse().ok()?),
+        };
+        Some(class_attrs![z_index: z_index])
+    }),
+    ("w-", |theme, value| {
+        Some(class_attrs![width: parse_screen_size(theme, value, Val::Vw(100.0))?])
+    }),
+    ("h-", |theme, value| {
+        Some(class_attrs![height: parse_screen_size(theme, value, Val::Vh(100.0))?])
+    }),
+    ("size-", |theme, value| {
+        let size = parse_theme_size(theme, value)?;
+        Some(class_attrs![width: size, height: size])
+    }),
+    ("min-w-", |theme, value| {
+        Some(class_attrs![min_width: parse_screen_size(theme, value, Val::Vw(100.0))?])
+    }),
+    ("min-h-", |theme, value| {
+        Some(class_attrs![min_height: parse_screen_size(theme, value, Val::Vh(100.0))?])
+    }),
+    ("max-w-", |theme, value| {
+        Some(class_attrs![max_width: parse_screen_size(theme, value, Val::Vw(100.0))?])
+    }),
+    ("max-h-", |theme, value| {
+        Some(class_attrs![max_height: parse_screen_size(theme, value, Val::Vh(100.0))?])
+    }),
+    ("basis-", |theme, value| Some(class_attrs![flex_basis: parse_theme_size(theme, value)?])),
+    ("grow-", |_, value| Some(class_attrs![flex_grow: parse_number(value)?])),
+    ("shrink-", |_, value| Some(class_attrs![flex_shrink: parse_number(value)?])),
+    ("aspect-", |_, value| {
+        let value = arbitrary_value(value).unwrap_or(value);
+        let ratio = match value.split_once('/') {
+            Some((width, height)) => {
+                width.trim().parse::<f32>().ok()? / height.trim().parse::<f32>().ok()?
+            }
+            None => value.parse().ok()?,
+        };
+        ratio.is_finite().then(|| class_attrs![aspect_ratio: Some(ratio)])
+    }),
+    ("gap-", |theme, value| {
+        let gap = parse_theme_size(theme, value)?;
+        Some(class_attrs![column_gap: gap, row_gap: gap])
+    }),
+    ("gap-x-", |theme, value| Some(class_attrs![column_gap: parse_theme_size(theme, value)?])),
+    ("gap-y-", |theme, value| Some(class_attrs![row_gap: parse_theme_size(theme, value)?])),
+    ("p-", |theme, value| {
+        let padding = parse_theme_size(theme, value)?;
+        Some(class_attrs![
+            padding_left: padding,
+            padding_right: padding,
+            padding_top: padding,
+            padding_bottom: padding,
+        ])
+    }),
+    ("px-", |theme, value| {
+        let padding = parse_theme_size(theme, value)?;
+        Some(class_attrs![padding_left: padding, padding_right: padding])
+    }),
+    ("py-", |theme, value| {
+        let padding = parse_theme_size(theme, value)?;
+        Some(class_attrs![padding_top: padding, padding_bottom: padding])
+    }),
+    ("pt-", |theme, value| Some(class_attrs![padding_top: parse_theme_size(theme, value)?])),
+    ("pr-", |theme, value| Some(class_attrs![padding_right: parse_theme_size(theme, value)?])),
+    ("pb-", |theme, value| Some(class_attrs![padding_bottom: parse_theme_size(theme, value)?])),
+    ("pl-", |theme, value| Some(class_attrs![padding_left: parse_theme_size(theme, value)?])),
+    ("m-", |theme, value| {
+        let margin = parse_theme_size(theme, value)?;
+        Some(class_attrs![
+            margin_left: margin,
+            margin_right: margin,
+            margin_top: margin,
+            margin_bottom: margin,
+        ])
+    }),
+    ("mx-", |theme, value| {
+        let margin = parse_theme_size(theme, value)?;
+        Some(class_attrs![margin_left: margin, margin_right: margin])
+    }),
+    ("my-", |theme, value| {
+        let margin = parse_theme_size(theme, value)?;
+        Some(class_attrs![margin_top: margin, margin_bottom: margin])
+    }),
+    ("mt-", |theme, value| Some(class_attrs![margin_top: parse_theme_size(theme, value)?])),
+    ("mr-", |theme, value| Some(class_attrs![margin_right: parse_theme_size(theme, value)?])),
+    ("mb-", |theme, value| Some(class_attrs![margin_bottom: parse_theme_size(theme, value)?])),
+    ("ml-", |theme, value| Some(class_attrs![margin_left: parse_theme_size(theme, value)?])),
+    ("inset-", |theme, value| {
+        let inset = parse_theme_size(theme, value)?;
+        Some(class_attrs![left: inset, right: inset, top: inset, bottom: inset])
+    }),
+    ("inset-x-", |theme, value| {
+        let inset = parse_theme_size(theme, value)?;
+        Some(class_attrs![left: inset, right: inset])
+    }),
+    ("inset-y-", |theme, value| {
+        let inset = parse_theme_size(theme, value)?;
+        Some(class_attrs![top: inset, bottom: inset])
+    }),
+    ("left-", |theme, value| Some(class_attrs![left: parse_theme_size(theme, value)?])),
+    ("right-", |theme, value| Some(class_attrs![right: parse_theme_size(theme, value)?])),
+    ("top-", |theme, value| Some(class_attrs![top: parse_theme_size(theme, value)?])),
+    ("bottom-", |theme, value| Some(class_attrs![bottom: parse_theme_size(theme, value)?])),
+    ("translate-x-", |theme, value| {
+        Some(class_attrs![translate_x: parse_theme_size(theme, value)?])
+    }),
+    ("translate-y-", |theme, value| {
+        Some(class_attrs![translate_y: parse_theme_size(theme, value)?])
+    }),
+    ("justify-", |_, value| {
+        Some(class_attrs![justify_content: match value {
+            "normal" => JustifyContent::Default,
+            "start" => JustifyContent::Start,
+            "end" => JustifyContent::End,
+            "center" => JustifyContent::Center,
+            "between" => JustifyContent::SpaceBetween,
+            "around" => JustifyContent::SpaceAround,
+            "evenly" => JustifyContent::SpaceEvenly,
+            _ => return None,
+        }])
+    }),
+    ("justify-items-", |_, value| {
+        Some(class_attrs![justify_items: match value {
+            "start" => JustifyItems::Start,
+            "end" => JustifyItems::End,
+            "center" => JustifyItems::Center,
+            "stretch" => JustifyItems::Stretch,
+            "baseline" => JustifyItems::Baseline,
+            _ => return None,
+        }])
+    }),
+    ("justify-self-", |_, value| {
+        Some(class_attrs![justify_self: match value {
+            "auto" => JustifySelf::Auto,
+            "start" => JustifySelf::Start,
+            "end" => JustifySelf::End,
+            "center" => JustifySelf::Center,
+            "stretch" => JustifySelf::Stretch,
+            "baseline" => JustifySelf::Baseline,
+            _ => return None,
+        }])
+    }),
+    ("items-", |_, value| {
+        Some(class_attrs![align_items: match value {
+            "start" => AlignItems::FlexStart,
+            "end" => AlignItems::FlexEnd,
+            "center" => AlignItems::Center,
+            "baseline" => AlignItems::Baseline,
+            "stretch" => AlignItems::Stretch,
+            _ => return None,
+        }])
+    }),
+    ("self-", |_, value| {
+        Some(class_attrs![align_self: match value {
+            "auto" => AlignSelf::Auto,
+            "start" => AlignSelf::FlexStart,
+            "end" => AlignSelf::FlexEnd,
+            "center" => AlignSelf::Center,
+            "stretch" => AlignSelf::Stretch,
+            "baseline" => AlignSelf::Baseline,
+            _ => return None,
+        }])
+    }),
+    ("content-", |_, value| {
+        Some(class_attrs![align_content: match value {
+            "normal" => AlignContent::Default,
+            "start" => AlignContent::FlexStart,
+            "end" => AlignContent::FlexEnd,
+            "center" => AlignContent::Center,
+            "between" => AlignContent::SpaceBetween,
+            "around" => AlignContent::SpaceAround,
+            "evenly" => AlignContent::SpaceEvenly,
+            "stretch" => AlignContent::Stretch,
+            _ => return None,
+        }])
+    }),
+    ("grid-cols-", |_, value| {
+        let count = value.parse::<u16>().ok().filter(|n| *n > 0)?;
+        Some(class_attrs![grid_template_columns: vec![RepeatedGridTrack::flex(count, 1.0)]])
+    }),
+    ("grid-rows-", |_, value| {
+        let count = value.parse::<u16>().ok().filter(|n| *n > 0)?;
+        Some(class_attrs![grid_template_rows: vec![RepeatedGridTrack::flex(count, 1.0)]])
+    }),
+    ("auto-cols-", |_, value| {
+        Some(class_attrs![grid_auto_columns: vec![parse_auto_track(value)?]])
+    }),
+    ("auto-rows-", |_, value| {
+        Some(class_attrs![grid_auto_rows: vec![parse_auto_track(value)?]])
+    }),
+    ("col-", |_, value| Some(class_attrs![grid_column: parse_grid_placement_class(value)?])),
+    ("col-start-", |_, value| Some(class_attrs![grid_column_start: parse_grid_line(value)?])),
+    ("col-end-", |_, value| Some(class_attrs![grid_column_end: parse_grid_line(value)?])),
+    ("row-", |_, value| Some(class_attrs![grid_row: parse_grid_placement_class(value)?])),
+    ("row-start-", |_, value| Some(class_attrs![grid_row_start: parse_grid_line(value)?])),
+    ("row-end-", |_, value| Some(class_attrs![grid_row_end: parse_grid_line(value)?])),
+    ("overflow-", |_, value| {
+        let overflow = from_str::<OverflowAxis>(value)?;
+        Some(class_attrs![overflow_x: overflow, overflow_y: overflow])
+    }),
+    ("overflow-x-", |_, value| Some(class_attrs![overflow_x: from_str(value)?])),
+    ("overflow-y-", |_, value| Some(class_attrs![overflow_y: from_str(value)?])),
+    ("bg-", |theme, value| {
+        Some(class_attrs![background: UiTexture::Color(parse_theme_color(theme, value)?)])
+    }),
+    ("text-", |theme, value| {
+        if let Some(color) = parse_theme_color(theme, value) {
+            return Some(class_attrs![text_color: color]);
+        }
+        let size = match theme.font_size(value) {
+            Some(size) => size,
+            None => match arbitrary_value(value) {
+                Some(value) => match parse_css_length(value)? {
+                    Val::Px(size) => size,
+                    _ => return None,
+                },
+                None => value.parse().ok()?,
+            },
+        };
+        Some(class_attrs![font_size: size])
+    }),
+    ("font-", |theme, value| {
+        let path = theme.fonts.get(value)?;
+        let handle = Handle::<Font>::weak(HandleId::from(AssetPath::from(path.as_str())));
+        Some(class_attrs![font: handle])
+    }),
+    ("border-", |theme, value| {
+        if let Some(color) = parse_theme_color(theme, value) {
+            return Some(class_attrs![border_color: BorderColor(color)]);
+        }
+        Some(border_widths(parse_border_width(value)?))
+    }),
+    ("border-x-", |_, value| {
+        let width = parse_border_width(value)?;
+        Some(class_attrs![border_left: width, border_right: width])
+    }),
+    ("border-y-", |_, value| {
+        let width = parse_border_width(value)?;
+        Some(class_attrs![border_top: width, border_bottom: width])
+    }),
+    ("border-l-", |_, value| Some(class_attrs![border_left: parse_border_width(value)?])),
+    ("border-r-", |_, value| Some(class_attrs![border_right: parse_border_width(value)?])),
+    ("border-t-", |_, value| Some(class_attrs![border_top: parse_border_width(value)?])),
+    ("border-b-", |_, value| Some(class_attrs![border_bottom: parse_border_width(value)?])),
+    ("rotate-", |_, value| {
+        let radians = match arbitrary_value(value) {
+            Some(value) => parse_angle(value)?,
+            None => value.parse::<f32>().ok()?.to_radians(),
+        };
+        // the ui is y-down, a positive z rotation is clockwise like css
+        Some(class_attrs![rotation: Quat::from_rotation_z(radians)])
+    }),
+    ("scale-", |_, value| {
+        let scale = match arbitrary_value(value) {
+            Some(value) => value.parse::<f32>().ok()?,
+            None => value.parse::<f32>().ok()? / 100.0,
+        };
+        Some(class_attrs![scale: Vec3::new(scale, scale, 1.0)])
+    }),
+    ("duration-", |_, value| Some(class_attrs![transition_duration: parse_ms(value)?])),
+    ("delay-", |_, value| Some(class_attrs![transition_delay: parse_ms(value)?])),
+    ("animate-", |theme, value| {
+        let spec = match arbitrary_value(value) {
+            Some(value) => from_str::<AnimationSpec>(&value.replace('_', " "))?,
+            None => match theme.animations.get(value) {
+                Some(shorthand) => from_str::<AnimationSpec>(shorthand)?,
+                None => AnimationSpec {
+                    name: value.to_string(),
+                    ..Default::default()
+                },
+            },
+        };
+        Some(class_attrs![animation: spec])
+    }),
+];
+
+/// Value utilities accepting a leading `-`, `-mt-2`
+const NEGATIVE_PREFIXES: &[&str] = &[
+    "z-", "m-", "mx-", "my-", "mt-", "mr-", "mb-", "ml-", "inset-", "inset-x-", "inset-y-", "left-",
+    "right-", "top-", "bottom-", "translate-x-", "translate-y-", "rotate-",
+];
+
+pub fn parse_class_inner(theme: &TailwindTheme, class: &str) -> ClassAttrs {
+    if let Some((_, utility)) = STATIC_UTILITIES.iter().find(|(name, _)| *name == class) {
+        return utility();
+    }
+    let (negative, class) = match class.strip_prefix('-') {
+        Some(class) => (true, class),
+        None => (false, class),
+    };
+    let Some((prefix, utility)) = VALUE_UTILITIES
+        .iter()
+        .filter(|(prefix, _)| class.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+    else {
+        return Default::default();
+    };
+    if negative && !NEGATIVE_PREFIXES.contains(prefix) {
+        return Default::default();
+    }
+    let mut attrs = utility(theme, &class[prefix.len()..]).unwrap_or_default();
+    if negative {
+        negate_values(&mut attrs);
+    }
+    attrs
+}
+
+fn negate_values(attrs: &mut ClassAttrs) {
+    for (_, value) in attrs.iter_mut() {
+        let value = value.deref_mut().as_reflect_mut();
+        if let Some(val) = value.downcast_mut::<Val>() {
+            *val = match *val {
+                Val::Px(n) => Val::Px(-n),
+                Val::Percent(n) => Val::Percent(-n),
+                Val::Vw(n) => Val::Vw(-n),
+                Val::Vh(n) => Val::Vh(-n),
+                Val::VMin(n) => Val::VMin(-n),
+                Val::VMax(n) => Val::VMax(-n),
+                Val::Auto => Val::Auto,
+            };
+        } else if let Some(rotation) = value.downcast_mut::<Quat>() {
+            *rotation = rotation.inverse();
+        } else if let Some(ZIndex::Global(index) | ZIndex::Local(index)) =
+            value.downcast_mut::<ZIndex>()
+        {
+            *index = -*index;
+        }
+    }
+}
+
+fn border_widths(width: Val) -> ClassAttrs {
+    class_attrs![
+        border_left: width,
+        border_right: width,
+        border_top: width,
+        border_bottom: width,
+    ]
+}
+
+fn transition_properties(properties: TransitionProperties) -> ClassAttrs {
+    class_attrs![transition: TransitionShorthand {
+        properties: Some(properties),
+        ..Default::default()
+    }]
+}
+
+/// Value of an arbitrary value class, `w-[37px]` → `37px`
+pub fn arbitrary_value(class: &str) -> Option<&str> {
+    class.strip_prefix('[')?.strip_suffix(']')
+}
+
+/// `37px`, `50%`, `10vw`, `auto`, a plain number is in pixels
+pub fn parse_css_length(s: &str) -> Option<Val> {
+    let number = |s: &str| s.parse::<f32>().ok();
+    Some(if s == "auto" {
+        Val::Auto
+    } else if let Some(px) = s.strip_suffix("px") {
+        Val::Px(number(px)?)
+    } else if let Some(percent) = s.strip_suffix('%') {
+        Val::Percent(number(percent)?)
+    } else if let Some(vmin) = s.strip_suffix("vmin") {
+        Val::VMin(number(vmin)?)
+    } else if let Some(vmax) = s.strip_suffix("vmax") {
+        Val::VMax(number(vmax)?)
+    } else if let Some(vw) = s.strip_suffix("vw") {
+        Val::Vw(number(vw)?)
+    } else if let Some(vh) = s.strip_suffix("vh") {
+        Val::Vh(number(vh)?)
+    } else {
+        Val::Px(number(s)?)
+    })
+}
+
+/// `4` spacing steps, `px` and other named spacing, `full`, `1/2`, `auto`, `50%`, `37px`, `[37px]`
+pub fn parse_theme_size(theme: &TailwindTheme, value: &str) -> Option<Val> {
+    if let Some(value) = arbitrary_value(value) {
+        return parse_css_length(value);
+    }
+    if let Some(px) = theme.spacing(value) {
+        return Some(Val::Px(px));
+    }
+    if value == "full" {
+        return Some(Val::Percent(100.0));
+    }
+    if let Some((numerator, denominator)) = value.split_once('/') {
+        let numerator = numerator.parse::<f32>().ok()?;
+        let denominator = denominator.parse::<f32>().ok().filter(|n| *n != 0.0)?;
+        return Some(Val::Percent(numerator / denominator * 100.0));
+    }
+    match value.parse::<f32>() {
+        Ok(steps) => Some(Val::Px(steps * theme.spacing_unit)),
+        Err(_) => parse_css_length(value),
+    }
+}
+
+/// Like [`parse_theme_size`], `screen` is the viewport size along the axis
+fn parse_screen_size(theme: &TailwindTheme, value: &str, screen: Val) -> Option<Val> {
+    match value {
+        "screen" => Some(screen),
+        value => parse_theme_size(theme, value),
+    }
+}
+
+pub fn parse_size_val(text: &str) -> Val {
+    parse_theme_size_val(TailwindTheme::builtin(), text)
+}
+
+pub fn parse_theme_size_val(theme: &TailwindTheme, text: &str) -> Val {
+    parse_theme_size(theme, text).unwrap_or(Val::Px(0.0))
+}
+
+fn parse_number(value: &str) -> Option<f32> {
+    arbitrary_value(value).unwrap_or(value).parse().ok()
+}
+
+/// Border widths are in pixels, `border-2`, `border-[3px]`
+fn parse_border_width(value: &str) -> Option<Val> {
+    match arbitrary_value(value) {
+        Some(value) => parse_css_length(value),
+        None => value.parse().ok().map(Val::Px),
+    }
+}
+
+/// `45deg`, `0.5rad`, `0.25turn`, in radians
+fn parse_angle(value: &str) -> Option<f32> {
+    if let Some(deg) = value.strip_suffix("deg") {
+        deg.parse::<f32>().ok().map(f32::to_radians)
+    } else if let Some(rad) = value.strip_suffix("rad") {
+        rad.parse().ok()
+    } else if let Some(turn) = value.strip_suffix("turn") {
+        turn.parse::<f32>().ok().map(|n| n * std::f32::consts::TAU)
+    } else {
+        value.parse::<f32>().ok().map(f32::to_radians)
+    }
+}
+
+/// `300` or `[0.3s]`, in milliseconds
+fn parse_ms(value: &str) -> Option<f32> {
+    match arbitrary_value(value) {
+        Some(value) => parse_duration_ms(value),
+        None => value.parse().ok(),
+    }
+}
+
+fn parse_auto_track(value: &str) -> Option<GridTrack> {
+    match value {
+        "auto" => Some(GridTrack::auto()),
+        "min" => Some(GridTrack::min_content()),
+        "max" => Some(GridTrack::max_content()),
+        "fr" => Some(GridTrack::fr(1.0)),
+        _ => None,
+    }
+}
+
+/// `span-2`, `span-full`, `start-2`, `end-3`, `auto` of `col-` / `row-` classes
+fn parse_grid_placement_class(class: &str) -> Option<GridPlacement> {
+    if class == "auto" {
+        Some(GridPlacement::auto())
+    } else if class == "span-full" {
+        Some(GridPlacement::start_end(1, -1))
+    } else if let Some(span) = class.strip_prefix("span-") {
+        Some(GridPlacement::span(span.parse().ok().filter(|n| *n != 0)?))
+    } else {
+        None
+    }
+}
+
+/// `col-start-*`, `row-end-*`, only set one line of the placement
+fn parse_grid_line(class: &str) -> Option<i64> {
+    class.parse::<i16>().ok().filter(|n| *n != 0).map(i64::from)
+}
@@ -16,7 +16,7 @@ impl<'w, 'e> SetAttrValueContext<'w, 'e> {
             .entities_extra_data
             .get(&entity)
             .map(|n| n.schema_name)?;
-        let schema_type = get_element_type(schema_name);
+        let schema_type = get_element_type(&self.element_registry, schema_name);
         let type_registry = self.type_registry.read();
         type_registry
             .get_type_data::<ReflectTextStyledElementType>(schema_type.type_id())
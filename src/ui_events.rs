@@ -0,0 +1,385 @@
+use std::fmt::Debug;
+use std::ops::Deref;
+
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::ui::UiStack;
+use bevy_mod_picking::backend::HitData;
+use bevy_mod_picking::events::{
+    Click, Down, Drag, DragEnd, DragEnter, DragLeave, DragOver, DragStart, Drop, Move, Out, Over,
+    Pointer, Up,
+};
+use bevy_mod_picking::focus::HoverMap;
+use bevy_mod_picking::pointer::PointerButton;
+use bevy_mod_picking::prelude::{EntityEvent, PointerId};
+
+use crate::drag_drop::find_draggable;
+use crate::elements::ScrollView;
+use crate::element_core::{DomEventData, UiEvent};
+use crate::impl_self_event_data;
+
+pub const WHEEL_LINE_HEIGHT: f32 = 20.0;
+pub const DOUBLE_CLICK_INTERVAL: f64 = 0.5;
+pub const DOUBLE_CLICK_DISTANCE: f32 = 4.0;
+
+pub fn ui_node_rect(world: &World, entity: Entity) -> Option<Rect> {
+    let node = world.get::<Node>(entity)?;
+    let transform = world.get::<GlobalTransform>(entity)?;
+    Some(Rect::from_center_size(
+        transform.translation().truncate(),
+        node.size(),
+    ))
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+impl Modifiers {
+    pub fn from_keys(keys: &Input<KeyCode>) -> Self {
+        Self {
+            shift: keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            ctrl: keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]),
+            alt: keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]),
+            meta: keys.any_pressed([KeyCode::SuperLeft, KeyCode::SuperRight]),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Reflect)]
+pub struct DoubleClick {
+    pub button: PointerButton,
+    pub hit: HitData,
+}
+
+pub trait PointerEventKind: Debug + Clone + Reflect + Send + Sync {
+    const IS_CLICK: bool = false;
+
+    fn button(&self) -> Option<PointerButton> {
+        None
+    }
+
+    /// Element being dragged for drag events, they are only dispatched when it is draggable
+    fn dragged(&self, _target: Entity) -> Option<Entity> {
+        None
+    }
+}
+
+macro_rules! impl_pointer_event_kind {
+    ($($type:ty),*) => {
+        $(
+            impl PointerEventKind for $type {}
+        )*
+    };
+    ($($type:ty),* ; button) => {
+        $(
+            impl PointerEventKind for $type {
+                fn button(&self) -> Option<PointerButton> {
+                    Some(self.button)
+                }
+            }
+        )*
+    };
+    ($($type:ty => |$event:ident, $target:ident| $dragged:expr),* ; drag) => {
+        $(
+            impl PointerEventKind for $type {
+                fn button(&self) -> Option<PointerButton> {
+                    Some(self.button)
+                }
+
+                fn dragged(&self, $target: Entity) -> Option<Entity> {
+                    let $event = self;
+                    Some($dragged)
+                }
+            }
+        )*
+    };
+}
+
+impl_pointer_event_kind!(Over, Out, Move);
+impl_pointer_event_kind!(Down, Up; button);
+impl_pointer_event_kind!(
+    DragStart => |_event, target| target,
+    Drag => |_event, target| target,
+    DragEnd => |_event, target| target,
+    DragEnter => |event, _target| event.dragged,
+    DragOver => |event, _target| event.dragged,
+    DragLeave => |event, _target| event.dragged,
+    Drop => |event, _target| event.dropped;
+    drag
+);
+
+impl PointerEventKind for Click {
+    const IS_CLICK: bool = true;
+
+    fn button(&self) -> Option<PointerButton> {
+        Some(self.button)
+    }
+}
+
+impl PointerEventKind for DoubleClick {
+    const IS_CLICK: bool = true;
+
+    fn button(&self) -> Option<PointerButton> {
+        Some(self.button)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PointerEventData<E> {
+    pub pointer_id: PointerId,
+    /// pointer position in logical pixels, relative to the window's top left
+    pub position: Vec2,
+    /// pointer position relative to the top left of the target's node
+    pub local_position: Vec2,
+    pub button: Option<PointerButton>,
+    /// number of consecutive clicks, only set for click and double click events
+    pub click_count: u32,
+    /// element being dragged, only set for drag events
+    pub dragged: Option<Entity>,
+    pub modifiers: Modifiers,
+    pub event: E,
+}
+
+impl<E> Deref for PointerEventData<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+pub type UiPointerEvent<E> = UiEvent<PointerEventData<E>>;
+
+impl<E> UiEvent<PointerEventData<E>> {
+    /// pointer position relative to the top left of the element whose handler is running
+    pub fn current_target_local_position(&self) -> Option<Vec2> {
+        self.current_target_rect()
+            .map(|rect| self.data().position - rect.min)
+    }
+}
+
+impl<E: PointerEventKind> DomEventData for Pointer<E> {
+    type Data = PointerEventData<E>;
+    const BLOCKED_BY_DISABLED: bool = true;
+
+    fn is_dispatched(&self, world: &World) -> bool {
+        self.event
+            .dragged(self.target)
+            .map_or(true, |dragged| find_draggable(world, dragged).is_some())
+    }
+
+    fn event_data(&self, world: &World) -> Self::Data {
+        let position = self.pointer_location.position;
+        let local_position = ui_node_rect(world, self.target)
+            .map(|rect| position - rect.min)
+            .unwrap_or(position);
+        let click_count = match self.event.button() {
+            Some(button) if E::IS_CLICK => world
+                .resource::<PointerClickCounter>()
+                .click_count(self.target, button, position),
+            _ => 0,
+        };
+        PointerEventData {
+            pointer_id: self.pointer_id,
+            position,
+            local_position,
+            button: self.event.button(),
+            click_count,
+            dragged: self.event.dragged(self.target),
+            modifiers: world
+                .get_resource::<Input<KeyCode>>()
+                .map(Modifiers::from_keys)
+                .unwrap_or_default(),
+            event: self.event.clone(),
+        }
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct PointerClickCounter {
+    last_click: Option<(Entity, PointerButton, f64, Vec2)>,
+    /// count of the last click
+    pub count: u32,
+    /// target, button, position and count of every click of this frame
+    frame_clicks: Vec<(Entity, PointerButton, Vec2, u32)>,
+}
+
+impl PointerClickCounter {
+    /// count of the click of this frame on `target` at `position`, 1 when it isn't found
+    pub fn click_count(&self, target: Entity, button: PointerButton, position: Vec2) -> u32 {
+        self.frame_clicks
+            .iter()
+            .rev()
+            .find(|(n_target, n_button, n_position, _)| {
+                *n_target == target && *n_button == button && *n_position == position
+            })
+            .map(|(_, _, _, count)| *count)
+            .unwrap_or(1)
+    }
+}
+
+pub fn count_pointer_clicks(
+    time: Res<Time>,
+    mut click_counter: ResMut<PointerClickCounter>,
+    mut clicks: EventReader<Pointer<Click>>,
+    mut double_clicks: EventWriter<Pointer<DoubleClick>>,
+) {
+    click_counter.frame_clicks.clear();
+    for click in clicks.iter() {
+        let now = time.elapsed_seconds_f64();
+        let position = click.pointer_location.position;
+        let is_repeated = click_counter
+            .last_click
+            .is_some_and(|(target, button, last_time, last_position)| {
+                target == click.target
+                    && button == click.event.button
+                    && now - last_time <= DOUBLE_CLICK_INTERVAL
+                    && position.distance(last_position) <= DOUBLE_CLICK_DISTANCE
+            });
+        click_counter.count = if is_repeated {
+            click_counter.count + 1
+        } else {
+            1
+        };
+        click_counter.last_click = Some((click.target, click.event.button, now, position));
+        let count = click_counter.count;
+        click_counter
+            .frame_clicks
+            .push((click.target, click.event.button, position, count));
+        if click_counter.count == 2 {
+            double_clicks.send(Pointer::new(
+                click.pointer_id,
+                click.pointer_location.clone(),
+                click.target,
+                DoubleClick {
+                    button: click.event.button,
+                    hit: click.event.hit.clone(),
+                },
+            ));
+        }
+    }
+}
+
+#[derive(Event, Clone, Debug, PartialEq, Reflect)]
+pub struct WheelEvent {
+    pub target: Entity,
+    pub delta: Vec2,
+}
+
+impl EntityEvent for WheelEvent {
+    fn target(&self) -> Entity {
+        self.target
+    }
+
+    fn can_bubble(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Event, Clone, Debug, PartialEq, Reflect)]
+pub struct ScrollEvent {
+    pub target: Entity,
+    pub scroll_left: f32,
+    pub scroll_top: f32,
+}
+
+impl EntityEvent for ScrollEvent {
+    fn target(&self) -> Entity {
+        self.target
+    }
+}
+
+pub fn get_hovered_ui_entity(hover_map: &HoverMap, ui_stack: &UiStack) -> Option<Entity> {
+    let hovered = hover_map.get(&PointerId::Mouse)?;
+    ui_stack
+        .uinodes
+        .iter()
+        .rev()
+        .find(|n| hovered.contains_key(n))
+        .copied()
+}
+
+pub fn dispatch_wheel_events(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut wheel_events: EventWriter<WheelEvent>,
+    hover_map: Res<HoverMap>,
+    ui_stack: Res<UiStack>,
+) {
+    for event in mouse_wheel_events.iter() {
+        let Some(target) = get_hovered_ui_entity(&hover_map, &ui_stack) else {
+            continue;
+        };
+        let delta = match event.unit {
+            MouseScrollUnit::Line => Vec2::new(event.x, event.y) * WHEEL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => Vec2::new(event.x, event.y),
+        };
+        wheel_events.send(WheelEvent { target, delta });
+    }
+}
+
+/// default action of [`WheelEvent`], skipped when a listener calls `prevent_default`
+pub fn scroll_nearest_scroll_view(event: &WheelEvent, world: &mut World) {
+    let mut scroll_views = world.query::<(&mut ScrollView, &Style)>();
+    let mut entity = Some(event.target);
+    while let Some(current) = entity {
+        if let Ok((mut scroll_view, style)) = scroll_views.get_mut(world, current) {
+            let offset = scroll_view.clamp_offset(scroll_view.offset - event.delta, style);
+            if offset != scroll_view.offset {
+                scroll_view.offset = offset;
+                return;
+            }
+        }
+        entity = world.get::<Parent>(current).map(|n| n.get());
+    }
+}
+
+#[derive(Event, Clone, Debug, PartialEq, Reflect)]
+pub struct TextInputEvent {
+    pub target: Entity,
+    pub value: String,
+    /// revision of the edit in [`InputState`](crate::elements::InputState), increasing per input
+    pub revision: u64,
+}
+
+impl EntityEvent for TextInputEvent {
+    fn target(&self) -> Entity {
+        self.target
+    }
+}
+
+#[derive(Event, Clone, Debug, PartialEq, Reflect)]
+pub struct TextChangeEvent {
+    pub target: Entity,
+    pub value: String,
+}
+
+impl EntityEvent for TextChangeEvent {
+    fn target(&self) -> Entity {
+        self.target
+    }
+}
+
+#[derive(Event, Clone, Debug, PartialEq, Reflect)]
+pub struct TextSubmitEvent {
+    pub target: Entity,
+    pub value: String,
+}
+
+impl EntityEvent for TextSubmitEvent {
+    fn target(&self) -> Entity {
+        self.target
+    }
+}
+
+impl_self_event_data!(
+    WheelEvent,
+    ScrollEvent,
+    TextInputEvent,
+    TextChangeEvent,
+    TextSubmitEvent
+);